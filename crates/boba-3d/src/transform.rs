@@ -1,5 +1,5 @@
 use boba_core::{
-    world::{Inserted, Link, PearlView, Removed},
+    world::{Inserted, Link, PearlView, Removed, Transferred},
     Pearl,
 };
 use extension_trait::extension_trait;
@@ -7,6 +7,8 @@ use glam::{Mat4, Quat, Vec3};
 use indexmap::IndexSet;
 
 pub type Iter<'a> = indexmap::set::Iter<'a, Link<Transform>>;
+/// Links collected from a hierarchy, so that the world can be modified while iterating.
+pub type Links = std::vec::IntoIter<Link<Transform>>;
 
 pub struct Transform {
     local_matrix: Mat4,
@@ -62,6 +64,18 @@ impl Pearl for Transform {
             pearl.world_mut().get_mut(child).unwrap().parent = parent_option;
         }
     }
+
+    fn on_transfer(mut pearl: Transferred<Self>) {
+        // keep only the relations to transforms that moved along with this one
+        let remap = pearl.remap();
+        let children = pearl.children.iter();
+        let children = children.filter_map(|child| remap.get(*child)).collect();
+        pearl.children = children;
+        pearl.parent = pearl.parent.and_then(|parent| remap.get(parent));
+
+        // world matrices are relative to the new parent, which may not have moved
+        pearl.sync_transforms();
+    }
 }
 
 impl Transform {
@@ -159,7 +173,7 @@ impl Transform {
         self.parent
     }
 
-    pub fn child_links(&self) -> Iter {
+    pub fn child_links(&self) -> Iter<'_> {
         self.children.iter()
     }
 }
@@ -237,6 +251,102 @@ pub impl<'a, 'world> TransformView<'a, 'world> for PearlView<'a, 'world, Transfo
         ChildWalker::new(self)
     }
 
+    /// Walks every descendant depth first, visiting each parent before its children.
+    fn walk_descendants(&mut self) -> DescendantWalker<'_, 'a, 'world> {
+        DescendantWalker::new(self)
+    }
+
+    /// Returns the links of the parent, grandparent and so on up to the root.
+    fn ancestors(&self) -> Links {
+        let mut ancestors = Vec::new();
+        let mut next = self.parent;
+        while let Some(link) = next {
+            ancestors.push(link);
+            next = self.world().get(link).unwrap().parent;
+        }
+        ancestors.into_iter()
+    }
+
+    /// Returns the links of every descendant depth first,
+    /// with each parent before its children.
+    fn descendants(&self) -> Links {
+        let mut descendants = Vec::new();
+        let mut stack: Vec<_> = self.children.iter().rev().copied().collect();
+        while let Some(link) = stack.pop() {
+            descendants.push(link);
+            let transform = self.world().get(link).unwrap();
+            stack.extend(transform.children.iter().rev());
+        }
+        descendants.into_iter()
+    }
+
+    /// Returns the links of every descendant breadth first,
+    /// ordered by their depth in the hierarchy.
+    fn descendants_bfs(&self) -> Links {
+        let mut descendants: Vec<_> = self.children.iter().copied().collect();
+        let mut current = 0;
+        while let Some(link) = descendants.get(current) {
+            let transform = self.world().get(*link).unwrap();
+            descendants.extend(transform.children.iter());
+            current += 1;
+        }
+        descendants.into_iter()
+    }
+
+    /// Returns the links of the other children of this transform's parent.
+    ///
+    /// A transform with no parent has no siblings.
+    fn siblings(&self) -> Links {
+        let Some(parent) = self.parent else {
+            return Vec::new().into_iter();
+        };
+
+        let link = self.link();
+        let parent = self.world().get(parent).unwrap();
+        let siblings = parent.children.iter().filter(|sibling| **sibling != link);
+        siblings.copied().collect::<Vec<_>>().into_iter()
+    }
+
+    /// Returns the link of the top most ancestor, or this transform's link if it has no parent.
+    fn root(&self) -> Link<Transform> {
+        let mut root = self.link();
+        let mut next = self.parent;
+        while let Some(link) = next {
+            root = link;
+            next = self.world().get(link).unwrap().parent;
+        }
+        root
+    }
+
+    /// Returns the number of ancestors above this transform.
+    fn depth(&self) -> usize {
+        let mut depth = 0;
+        let mut next = self.parent;
+        while let Some(link) = next {
+            depth += 1;
+            next = self.world().get(link).unwrap().parent;
+        }
+        depth
+    }
+
+    /// Returns `true` if the transform at `link` is a descendant of this transform.
+    fn is_ancestor_of(&self, link: Link<Transform>) -> bool {
+        let this = self.link();
+        let mut next = self
+            .world()
+            .get(link)
+            .and_then(|transform| transform.parent);
+        while let Some(link) = next {
+            if link == this {
+                return true;
+            }
+
+            next = self.world().get(link).unwrap().parent;
+        }
+
+        false
+    }
+
     fn set_local_pos(&mut self, pos: Vec3) {
         if self.local_pos == pos {
             return;
@@ -294,7 +404,7 @@ pub impl<'a, 'world> TransformView<'a, 'world> for PearlView<'a, 'world, Transfo
         }
 
         self.sync_transforms();
-        return true;
+        true
     }
 
     fn set_parent_no_sync(&mut self, parent: Link<Transform>) -> bool {
@@ -325,3 +435,118 @@ impl<'a, 'source, 'world> ChildWalker<'a, 'source, 'world> {
         Some(self.view.get_view(link).unwrap())
     }
 }
+
+/// Walks the descendants of a transform depth first, yielding a view of each one.
+///
+/// The children of each descendant are collected on the call after it is visited,
+/// so changes made to a descendant's children while visiting it are reflected in the rest of the walk.
+/// Descendants that are removed before they are visited are skipped.
+pub struct DescendantWalker<'a, 'source, 'world> {
+    view: &'a mut PearlView<'source, 'world, Transform>,
+    stack: Vec<Link<Transform>>,
+    last: Option<Link<Transform>>,
+}
+
+impl<'a, 'source, 'world> DescendantWalker<'a, 'source, 'world> {
+    pub fn new(view: &'a mut PearlView<'source, 'world, Transform>) -> Self {
+        let stack = view.child_links().rev().copied().collect();
+        Self {
+            view,
+            stack,
+            last: None,
+        }
+    }
+
+    pub fn walk_next(&mut self) -> Option<PearlView<'_, 'world, Transform>> {
+        // collect the children of the last descendant now that it is no longer being visited
+        if let Some(last) = self.last.take() {
+            if let Some(transform) = self.view.world().get(last) {
+                self.stack.extend(transform.children.iter().rev());
+            }
+        }
+
+        loop {
+            let link = self.stack.pop()?;
+            if !self.view.world().contains(link) {
+                continue;
+            }
+
+            self.last = Some(link);
+            return Some(self.view.get_view(link).unwrap());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use boba_core::{world::WorldQueue, World};
+
+    use super::*;
+
+    #[test]
+    fn traverse_hierarchy() {
+        let mut world = World::new();
+        let root = world.insert(Transform::new());
+        let a = world.insert(Transform::new());
+        let b = world.insert(Transform::new());
+        let c = world.insert(Transform::from_pos(Vec3::X));
+        let d = world.insert(Transform::new());
+
+        let mut queue = WorldQueue::new(&mut world);
+        for (child, parent) in [(a, root), (b, root), (c, a), (d, b)] {
+            let mut view = PearlView::new(child, &mut queue).unwrap();
+            assert!(view.set_parent(parent));
+        }
+
+        let mut view = PearlView::new(root, &mut queue).unwrap();
+        assert!(view.descendants().eq([a, c, b, d]));
+        assert!(view.descendants_bfs().eq([a, b, c, d]));
+        assert!(view.is_ancestor_of(c));
+        assert!(!view.is_ancestor_of(root));
+        assert!(view.depth() == 0 && view.root() == root);
+
+        // move every descendant while walking the hierarchy,
+        // moving d under a while a is being visited
+        let mut visited = Vec::new();
+        let mut walker = view.walk_descendants();
+        while let Some(mut descendant) = walker.walk_next() {
+            visited.push(descendant.link());
+            let pos = descendant.local_pos();
+            descendant.set_local_pos(pos + Vec3::Y);
+            if descendant.link() == a {
+                assert!(descendant.get_view(d).unwrap().set_parent(a));
+            }
+        }
+        assert!(visited == [a, c, d, b]);
+
+        let view = PearlView::new(c, &mut queue).unwrap();
+        assert!(view.ancestors().eq([a, root]));
+        assert!(view.depth() == 2 && view.root() == root);
+        assert!(!view.is_ancestor_of(a));
+        assert!(view.world_pos() == Vec3::new(1., 2., 0.));
+        assert!(view.siblings().eq([d]));
+
+        let view = PearlView::new(a, &mut queue).unwrap();
+        assert!(view.siblings().eq([b]));
+    }
+
+    #[test]
+    fn transfer_child() {
+        let mut world = World::new();
+        let parent = world.insert(Transform::from_pos(Vec3::X));
+        let child = world.insert(Transform::from_pos(Vec3::Y));
+
+        let mut queue = WorldQueue::new(&mut world);
+        let mut view = PearlView::new(child, &mut queue).unwrap();
+        assert!(view.set_parent(parent));
+        assert!(view.world_pos() == Vec3::new(1., 1., 0.));
+        drop(queue);
+
+        // the parent stays behind, so the child is detached and moved back to its local position
+        let mut other = World::new();
+        let child = world.transfer(child, &mut other).unwrap();
+        let transform = other.get(child).unwrap();
+        assert!(transform.parent_link().is_none());
+        assert!(transform.world_pos() == Vec3::Y);
+    }
+}
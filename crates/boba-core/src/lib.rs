@@ -1,4 +1,8 @@
 pub mod pearl;
+pub mod prefab;
+pub mod query;
+#[cfg(feature = "tracing")]
+pub mod trace;
 pub mod world;
 
 pub use pearl::Pearl;
@@ -1,16 +1,104 @@
-use crate::world::{Inserted, PearlView, Removed};
+use std::marker::PhantomData;
+
+use crate::{
+    query::{Query, Responder},
+    world::{BatchView, Inserted, PearlView, Removed, Transferred},
+};
 
 pub trait Event: 'static {}
 impl<T: 'static> Event for T {}
 
+/// A family of events that may borrow data, keyed by a `'static` marker type.
+///
+/// This allows triggering events that are not `'static`,
+/// as the marker type is used to identify the event instead of the event itself.
+pub trait EventFamily: 'static {
+    type Event<'a>;
+
+    /// Returns the name used to identify this family in diagnostics.
+    fn name() -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Returns `true` if `event` has stopped, so that no more listeners are triggered with it.
+    fn is_done(_event: &Self::Event<'_>) -> bool {
+        false
+    }
+}
+
+/// The [`EventFamily`] for a `'static` [`Event`].
+pub struct Owned<E: Event> {
+    _type: PhantomData<fn() -> E>,
+}
+
+impl<E: Event> EventFamily for Owned<E> {
+    type Event<'a> = E;
+
+    fn name() -> &'static str {
+        std::any::type_name::<E>()
+    }
+}
+
 pub trait Listener<E: Event>: Pearl {
     fn trigger(pearl: PearlView<Self>, event: &mut E);
 }
 
+/// A listener for events in an [`EventFamily`].
+pub trait FamilyListener<F: EventFamily>: Pearl {
+    fn trigger(pearl: PearlView<Self>, event: &mut F::Event<'_>);
+}
+
+impl<E: Event, P: Listener<E>> FamilyListener<Owned<E>> for P {
+    fn trigger(pearl: PearlView<Self>, event: &mut E) {
+        <P as Listener<E>>::trigger(pearl, event)
+    }
+}
+
+/// A listener that receives every pearl of its type in a single call.
+///
+/// Unlike [`Listener`], which is triggered once per pearl,
+/// `trigger_all` is called once per event with a [`BatchView`] over the whole pearl storage.
+pub trait BatchListener<E: Event>: Pearl {
+    fn trigger_all(pearls: BatchView<Self>, event: &mut E);
+}
+
 pub trait EventSource<P> {
     fn listen<E: Event>(&mut self)
     where
         P: Listener<E>;
+
+    /// Registers `P` as a [`FamilyListener`] for `F`.
+    fn listen_family<F: EventFamily>(&mut self)
+    where
+        P: FamilyListener<F>;
+
+    /// Registers `P` as a [`Listener`] for `E` that only triggers subscribed pearls.
+    ///
+    /// Pearls are subscribed individually using [`PearlView::subscribe`].
+    fn listen_subscribed<E: Event>(&mut self)
+    where
+        P: Listener<E>;
+
+    /// Registers `P` as a [`Responder`] for the query `Q`.
+    fn respond<Q: Query>(&mut self)
+    where
+        P: Responder<Q>;
+
+    /// Opts `P` into world snapshots.
+    ///
+    /// Snapshots may be shared between threads, so `P` must be [`Send`] and [`Sync`].
+    /// See [`World::snapshot`](crate::World::snapshot) for more details.
+    fn enable_snapshots(&mut self)
+    where
+        P: Clone + Send + Sync;
+
+    /// Registers `P` as a [`BatchListener`] for `E`.
+    ///
+    /// A pearl type has a single runner per event,
+    /// so this replaces any [`Listener`] registered by `P` for the same event.
+    fn listen_batch<E: Event>(&mut self)
+    where
+        P: BatchListener<E>;
 }
 
 #[allow(unused_variables)]
@@ -18,4 +106,18 @@ pub trait Pearl: Sized + 'static {
     fn register(source: &mut impl EventSource<Self>) {}
     fn on_insert(pearl: Inserted<Self>) {}
     fn on_remove(pearl: Removed<Self>) {}
+
+    /// Called after the pearl has been moved into a new world,
+    /// using [`World::transfer`](crate::World::transfer) or [`World::merge`](crate::World::merge).
+    ///
+    /// Any links held by the pearl should be updated here using [`Transferred::remap`].
+    fn on_transfer(pearl: Transferred<Self>) {}
+}
+
+/// A pearl whose values can be kept in a pool and reused after removal.
+///
+/// Pools are enabled per type using [`World::enable_pool`](crate::World::enable_pool).
+pub trait Poolable: Pearl + Default {
+    /// Resets a recycled value so that it can be spawned again.
+    fn reset(&mut self);
 }
@@ -0,0 +1,348 @@
+use std::{any::TypeId, rc::Rc};
+
+use hashbrown::HashMap;
+
+use crate::{
+    world::{Link, PearlView, WorldQueue},
+    Pearl, World,
+};
+
+type SpawnFn = Box<dyn Fn(&mut World, &PrefabLinks) -> Link<()>>;
+type HookFn = Box<dyn Fn(&mut WorldQueue, &PrefabLinks)>;
+
+enum Entry {
+    Pearl {
+        id: String,
+        type_id: TypeId,
+        spawn: SpawnFn,
+    },
+    Nested {
+        id: String,
+        prefab: Rc<Prefab>,
+    },
+}
+
+/// The links created when spawning a [`Prefab`], keyed by their local ids.
+///
+/// Links created by nested prefabs are keyed as `"{nested_id}/{local_id}"`.
+#[derive(Default)]
+pub struct PrefabLinks {
+    links: HashMap<String, (TypeId, Link<()>)>,
+}
+
+impl PrefabLinks {
+    pub fn len(&self) -> usize {
+        self.links.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    /// Returns the link for the local `id`.
+    ///
+    /// Returns `None` if there is no pearl of type `P` with that id.
+    pub fn get<P: Pearl>(&self, id: &str) -> Option<Link<P>> {
+        match self.links.get(id) {
+            Some((type_id, link)) if *type_id == TypeId::of::<P>() => Some(link.into_type()),
+            _ => None,
+        }
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.links.keys().map(|id| id.as_str())
+    }
+
+    fn insert(&mut self, id: String, type_id: TypeId, link: Link<()>) {
+        if self.links.insert(id, (type_id, link)).is_some() {
+            log::warn!("Prefab contains duplicate local ids. Only the last one will be linked.");
+        }
+    }
+}
+
+/// A template for a group of linked pearls that can be spawned any number of times.
+///
+/// Pearls are spawned in the order they were added,
+/// so a pearl can only link to pearls that were added before it.
+/// Any links that need to point forward can be resolved in an [`add_then`](Self::add_then) hook,
+/// which runs after every pearl in the prefab has been spawned.
+#[derive(Default)]
+pub struct Prefab {
+    entries: Vec<Entry>,
+    hooks: Vec<HookFn>,
+}
+
+impl Prefab {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds a pearl with the local `id`, created using `f` every time the prefab is spawned.
+    pub fn add<P: Pearl>(
+        &mut self,
+        id: impl Into<String>,
+        f: impl Fn(&PrefabLinks) -> P + 'static,
+    ) -> &mut Self {
+        self.entries.push(Entry::Pearl {
+            id: id.into(),
+            type_id: TypeId::of::<P>(),
+            spawn: Box::new(move |world, links| world.insert(f(links)).into_type()),
+        });
+        self
+    }
+
+    /// Adds a pearl like [`add`](Self::add),
+    /// and runs `then` on it after the whole prefab has been spawned.
+    pub fn add_then<P: Pearl>(
+        &mut self,
+        id: impl Into<String>,
+        f: impl Fn(&PrefabLinks) -> P + 'static,
+        then: impl Fn(PearlView<P>, &PrefabLinks) + 'static,
+    ) -> &mut Self {
+        let id = id.into();
+        self.add(id.clone(), f);
+        self.hooks.push(Box::new(move |world, links| {
+            let Some(link) = links.get::<P>(&id) else {
+                return;
+            };
+
+            if let Some(view) = PearlView::new(link, world) {
+                then(view, links);
+            }
+        }));
+        self
+    }
+
+    /// Adds a nested prefab whose links will be prefixed with `id`.
+    pub fn nest(&mut self, id: impl Into<String>, prefab: impl Into<Rc<Prefab>>) -> &mut Self {
+        self.entries.push(Entry::Nested {
+            id: id.into(),
+            prefab: prefab.into(),
+        });
+        self
+    }
+
+    /// Spawns every pearl in this prefab into `world`, and returns their links.
+    pub fn spawn(&self, world: &mut World) -> PrefabLinks {
+        let mut links = PrefabLinks::default();
+        for entry in self.entries.iter() {
+            match entry {
+                Entry::Pearl { id, type_id, spawn } => {
+                    let link = spawn(world, &links);
+                    links.insert(id.clone(), *type_id, link);
+                }
+                Entry::Nested { id, prefab } => {
+                    for (local_id, (type_id, link)) in prefab.spawn(world).links {
+                        links.insert(format!("{id}/{local_id}"), type_id, link);
+                    }
+                }
+            }
+        }
+
+        let mut queue = WorldQueue::new(world);
+        for hook in self.hooks.iter() {
+            hook(&mut queue, &links);
+        }
+        drop(queue);
+
+        links
+    }
+}
+
+#[cfg(feature = "serde")]
+pub use data::*;
+
+#[cfg(feature = "serde")]
+mod data {
+    use std::fmt::Display;
+
+    use serde::Deserialize;
+    use serde_json::Value;
+
+    use super::*;
+
+    type LoadFn = Box<dyn Fn(&mut Prefab, String, Value)>;
+
+    /// The error returned when a prefab could not be loaded from data.
+    #[derive(Debug)]
+    pub enum PrefabError {
+        /// The data could not be parsed.
+        Parse(serde_json::Error),
+        /// An entry has neither a `type` nor a `prefab` field.
+        MissingKind(String),
+        /// An entry references a pearl type that is not registered.
+        UnknownType(String),
+        /// An entry references a nested prefab that is not registered.
+        UnknownPrefab(String),
+    }
+
+    impl Display for PrefabError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Parse(error) => write!(f, "failed to parse prefab. Error: {error}"),
+                Self::MissingKind(id) => write!(f, "prefab entry '{id}' has no type or prefab"),
+                Self::UnknownType(name) => write!(f, "pearl type '{name}' is not registered"),
+                Self::UnknownPrefab(name) => write!(f, "prefab '{name}' is not registered"),
+            }
+        }
+    }
+
+    impl std::error::Error for PrefabError {}
+
+    #[derive(Deserialize)]
+    struct PrefabData {
+        pearls: Vec<EntryData>,
+    }
+
+    #[derive(Deserialize)]
+    struct EntryData {
+        id: String,
+        #[serde(rename = "type")]
+        type_name: Option<String>,
+        prefab: Option<String>,
+        #[serde(default)]
+        data: Value,
+    }
+
+    /// A registry of named pearl constructors and prefabs used to load prefabs from data.
+    ///
+    /// Prefab data is JSON in the form:
+    /// ```json
+    /// { "pearls": [
+    ///     { "id": "transform", "type": "Transform", "data": { ... } },
+    ///     { "id": "gun", "prefab": "gun" }
+    /// ] }
+    /// ```
+    #[derive(Default)]
+    pub struct PrefabRegistry {
+        pearls: HashMap<String, LoadFn>,
+        prefabs: HashMap<String, Rc<Prefab>>,
+    }
+
+    impl PrefabRegistry {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Registers a pearl type with `name`, created from its entry data using `f`.
+        pub fn register_pearl<P: Pearl>(
+            &mut self,
+            name: impl Into<String>,
+            f: impl Fn(&Value, &PrefabLinks) -> P + 'static,
+        ) {
+            let f = Rc::new(f);
+            let load: LoadFn = Box::new(move |prefab, id, data| {
+                let f = f.clone();
+                prefab.add(id, move |links| f(&data, links));
+            });
+            self.pearls.insert(name.into(), load);
+        }
+
+        /// Registers a prefab with `name` so it can be nested by other prefab data.
+        pub fn register_prefab(&mut self, name: impl Into<String>, prefab: impl Into<Rc<Prefab>>) {
+            self.prefabs.insert(name.into(), prefab.into());
+        }
+
+        /// Loads a prefab from JSON `data`.
+        pub fn load(&self, data: &str) -> Result<Prefab, PrefabError> {
+            let data: PrefabData = serde_json::from_str(data).map_err(PrefabError::Parse)?;
+            let mut prefab = Prefab::new();
+            for entry in data.pearls {
+                match (entry.type_name, entry.prefab) {
+                    (Some(name), _) => match self.pearls.get(&name) {
+                        Some(load) => load(&mut prefab, entry.id, entry.data),
+                        None => return Err(PrefabError::UnknownType(name)),
+                    },
+                    (None, Some(name)) => match self.prefabs.get(&name) {
+                        Some(nested) => {
+                            prefab.nest(entry.id, nested.clone());
+                        }
+                        None => return Err(PrefabError::UnknownPrefab(name)),
+                    },
+                    (None, None) => return Err(PrefabError::MissingKind(entry.id)),
+                }
+            }
+
+            Ok(prefab)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Node {
+        parent: Option<Link<Node>>,
+    }
+
+    impl Pearl for Node {}
+
+    #[test]
+    fn spawn_nested() {
+        let mut child = Prefab::new();
+        child.add("leaf", |_| Node { parent: None });
+
+        let mut prefab = Prefab::new();
+        prefab.add("root", |_| Node { parent: None });
+        prefab.nest("child", child);
+        prefab.add_then(
+            "branch",
+            |links| Node {
+                parent: links.get("root"),
+            },
+            |mut view, links| {
+                let leaf = links.get::<Node>("child/leaf").unwrap();
+                let link = view.link();
+                view.world_mut().get_mut(leaf).unwrap().parent = Some(link);
+            },
+        );
+
+        let mut world = World::new();
+        let first = world.spawn_prefab(&prefab);
+        let second = world.spawn_prefab(&prefab);
+        assert!(world.len::<Node>() == 6);
+        assert!(first.len() == 3);
+
+        let root = first.get::<Node>("root").unwrap();
+        let branch = first.get::<Node>("branch").unwrap();
+        let leaf = first.get::<Node>("child/leaf").unwrap();
+        assert!(world.get(branch).unwrap().parent == Some(root));
+        assert!(world.get(leaf).unwrap().parent == Some(branch));
+        assert!(second.get::<Node>("root") != Some(root));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn load_data() {
+        let mut registry = PrefabRegistry::new();
+        registry.register_pearl("Node", |data, links| Node {
+            parent: data["parent"].as_str().and_then(|id| links.get(id)),
+        });
+
+        let prefab = registry
+            .load(
+                r#"{ "pearls": [
+                    { "id": "root", "type": "Node" },
+                    { "id": "child", "type": "Node", "data": { "parent": "root" } }
+                ] }"#,
+            )
+            .unwrap();
+
+        let mut world = World::new();
+        let links = world.spawn_prefab(&prefab);
+        let child = links.get::<Node>("child").unwrap();
+        assert!(world.get(child).unwrap().parent == links.get("root"));
+        assert!(registry
+            .load(r#"{ "pearls": [{ "id": "x", "type": "Missing" }] }"#)
+            .is_err());
+    }
+}
@@ -0,0 +1,158 @@
+use std::{marker::PhantomData, ops::ControlFlow};
+
+use crate::{
+    pearl::{EventFamily, FamilyListener},
+    world::PearlView,
+    Pearl,
+};
+
+/// A question that can be asked of every [`Responder`] in a world.
+pub trait Query: 'static {
+    type Response;
+}
+
+pub trait Responder<Q: Query>: Pearl {
+    fn respond(pearl: PearlView<Self>, query: &Q) -> Option<Q::Response>;
+}
+
+/// The event that carries a [`Query`] through the world and collects its responses.
+pub struct QueryEvent<'a, Q: Query> {
+    query: &'a Q,
+    reducer: &'a mut dyn FnMut(Q::Response) -> ControlFlow<()>,
+    done: bool,
+}
+
+impl<'a, Q: Query> QueryEvent<'a, Q> {
+    pub(crate) fn new(
+        query: &'a Q,
+        reducer: &'a mut dyn FnMut(Q::Response) -> ControlFlow<()>,
+    ) -> Self {
+        Self {
+            query,
+            reducer,
+            done: false,
+        }
+    }
+
+    pub fn query(&self) -> &Q {
+        self.query
+    }
+
+    /// Returns `true` if the query has been short-circuited.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+/// The [`EventFamily`] used to dispatch a [`Query`].
+pub struct QueryFamily<Q: Query> {
+    _type: PhantomData<fn() -> Q>,
+}
+
+impl<Q: Query> EventFamily for QueryFamily<Q> {
+    type Event<'a> = QueryEvent<'a, Q>;
+
+    fn is_done(event: &Self::Event<'_>) -> bool {
+        event.done
+    }
+}
+
+impl<Q: Query, P: Responder<Q>> FamilyListener<QueryFamily<Q>> for P {
+    fn trigger(pearl: PearlView<Self>, event: &mut QueryEvent<'_, Q>) {
+        if event.done {
+            return;
+        }
+
+        if let Some(response) = P::respond(pearl, event.query) {
+            event.done = (event.reducer)(response).is_break();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use crate::{pearl::EventSource, World};
+
+    use super::*;
+
+    struct Weight;
+    impl Query for Weight {
+        type Response = u32;
+    }
+
+    thread_local! {
+        static RESPONSES: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct Item(u32);
+
+    impl Pearl for Item {
+        fn register(source: &mut impl EventSource<Self>) {
+            source.respond::<Weight>();
+        }
+    }
+
+    impl Responder<Weight> for Item {
+        fn respond(pearl: PearlView<Self>, _: &Weight) -> Option<u32> {
+            RESPONSES.set(RESPONSES.get() + 1);
+            match pearl.0 {
+                0 => None,
+                weight => Some(weight),
+            }
+        }
+    }
+
+    #[test]
+    fn reducers() {
+        let mut world = World::new();
+        world.insert(Item(0));
+        world.insert(Item(3));
+        world.insert(Item(4));
+
+        assert!(world.query_event(&Weight) == [3, 4]);
+        assert!(world.query_sum(&Weight) == 7);
+        assert!(world.query_first(&Weight) == Some(3));
+
+        let mut visited = 0;
+        world.query_fold(&Weight, (), |_, _| {
+            visited += 1;
+            ControlFlow::Break(())
+        });
+        assert!(visited == 1);
+    }
+
+    struct Crate;
+
+    impl Pearl for Crate {
+        fn register(source: &mut impl EventSource<Self>) {
+            source.respond::<Weight>();
+        }
+    }
+
+    impl Responder<Weight> for Crate {
+        fn respond(_: PearlView<Self>, _: &Weight) -> Option<u32> {
+            RESPONSES.set(RESPONSES.get() + 1);
+            Some(10)
+        }
+    }
+
+    #[test]
+    fn short_circuit() {
+        let mut world = World::new();
+        world.insert(Item(0));
+        world.insert(Item(3));
+        world.insert(Item(4));
+        world.insert(Crate);
+
+        // no responder is asked after the query stops, of the same type or any other
+        RESPONSES.set(0);
+        assert!(world.query_first(&Weight) == Some(3));
+        assert!(RESPONSES.get() == 2);
+
+        RESPONSES.set(0);
+        assert!(world.query_sum(&Weight) == 17);
+        assert!(RESPONSES.get() == 4);
+    }
+}
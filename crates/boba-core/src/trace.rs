@@ -0,0 +1,259 @@
+use std::{
+    fmt::Debug,
+    io,
+    marker::PhantomData,
+    ops::Range,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use serde_json::{json, Map, Value};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, EnteredSpan, Id, Record},
+    Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+use crate::{pearl::EventFamily, Pearl};
+
+/// The name of the span that wraps a single frame.
+///
+/// Frames are counted by the [`ChromeRecorder`] every time a span with this name exits.
+/// The milk-tea event loop opens one of these for each loop iteration,
+/// and every app event dispatched during that iteration runs inside it.
+pub const FRAME_SPAN: &str = "frame";
+
+/// The span around a single listener runner, created while dispatching an event.
+pub(crate) struct ListenerSpan<P, F> {
+    span: EnteredSpan,
+    start: Instant,
+    _type: PhantomData<fn() -> (P, F)>,
+}
+
+impl<P: Pearl, F: EventFamily> ListenerSpan<P, F> {
+    pub fn enter() -> Self {
+        let span = tracing::trace_span!(
+            "listener",
+            event = F::name(),
+            pearl = std::any::type_name::<P>(),
+            count = tracing::field::Empty,
+            elapsed_us = tracing::field::Empty,
+        );
+
+        Self {
+            span: span.entered(),
+            start: Instant::now(),
+            _type: PhantomData,
+        }
+    }
+
+    /// Records the number of pearls visited and the elapsed time, then exits the span.
+    pub fn exit(self, count: usize) {
+        let elapsed = self.start.elapsed().as_micros() as u64;
+        self.span.record("count", count);
+        self.span.record("elapsed_us", elapsed);
+    }
+}
+
+#[derive(Default, Clone)]
+struct Args(Map<String, Value>);
+
+impl Visit for Args {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().into(), format!("{value:?}").into());
+    }
+}
+
+struct Entered(Instant);
+
+struct RecorderState {
+    epoch: Instant,
+    frames: Range<u64>,
+    frame: u64,
+    events: Vec<Value>,
+}
+
+/// A [`Layer`] that records spans as Chrome trace JSON for a range of frames.
+///
+/// The recorded trace can be opened using `chrome://tracing` or [Perfetto](https://ui.perfetto.dev).
+/// Frames are delimited by spans named [`FRAME_SPAN`].
+#[derive(Clone)]
+pub struct ChromeRecorder {
+    state: Arc<Mutex<RecorderState>>,
+}
+
+impl ChromeRecorder {
+    /// Creates a recorder for the `frames`, counted from when the recorder is created.
+    pub fn new(frames: Range<u64>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(RecorderState {
+                epoch: Instant::now(),
+                frames,
+                frame: 0,
+                events: Vec::new(),
+            })),
+        }
+    }
+
+    /// Returns the number of frames that have completed.
+    pub fn frame(&self) -> u64 {
+        self.state.lock().unwrap().frame
+    }
+
+    /// Returns `true` if every frame in the range has been recorded.
+    pub fn is_finished(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.frame >= state.frames.end
+    }
+
+    /// Returns the recorded trace as Chrome trace JSON.
+    pub fn to_json(&self) -> String {
+        let state = self.state.lock().unwrap();
+        json!({ "traceEvents": state.events }).to_string()
+    }
+
+    /// Writes the recorded trace as Chrome trace JSON to the file at `path`.
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+}
+
+impl<S> Layer<S> for ChromeRecorder
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut args = Args::default();
+        attrs.record(&mut args);
+        span.extensions_mut().insert(args);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut extensions = span.extensions_mut();
+        if let Some(args) = extensions.get_mut::<Args>() {
+            values.record(args);
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().replace(Entered(Instant::now()));
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut state = self.state.lock().unwrap();
+        let extensions = span.extensions();
+        if let (true, Some(Entered(start))) = (
+            state.frames.contains(&state.frame),
+            extensions.get::<Entered>(),
+        ) {
+            // name listener spans after their pearl, and trigger spans after their event
+            let args = extensions.get::<Args>().cloned().unwrap_or_default().0;
+            let name = ["pearl", "event"]
+                .into_iter()
+                .find_map(|key| args.get(key).and_then(Value::as_str))
+                .unwrap_or(span.name())
+                .to_string();
+
+            let event = json!({
+                "name": name,
+                "cat": span.name(),
+                "ph": "X",
+                "ts": start.duration_since(state.epoch).as_micros() as u64,
+                "dur": start.elapsed().as_micros() as u64,
+                "pid": 1,
+                "tid": 1,
+                "args": args,
+            });
+            state.events.push(event);
+        }
+
+        if span.name() == FRAME_SPAN {
+            state.frame += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use crate::{
+        pearl::{EventSource, Listener},
+        world::PearlView,
+        World,
+    };
+
+    use super::*;
+
+    struct Tick;
+
+    struct Ticker;
+
+    impl Pearl for Ticker {
+        fn register(source: &mut impl EventSource<Self>) {
+            source.listen::<Tick>();
+        }
+    }
+
+    impl Listener<Tick> for Ticker {
+        fn trigger(_: PearlView<Self>, _: &mut Tick) {}
+    }
+
+    #[test]
+    fn record_frames() {
+        let recorder = ChromeRecorder::new(1..2);
+        let subscriber = tracing_subscriber::registry().with(recorder.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            let mut world = World::new();
+            world.insert(Ticker);
+            world.insert(Ticker);
+            for _ in 0..3 {
+                let _frame = tracing::info_span!(FRAME_SPAN).entered();
+                world.trigger(&mut Tick);
+            }
+        });
+
+        assert!(recorder.frame() == 3);
+        assert!(recorder.is_finished());
+
+        // only the second frame is recorded, with one trigger and one listener span
+        let trace: Value = serde_json::from_str(&recorder.to_json()).unwrap();
+        let events = trace["traceEvents"].as_array().unwrap();
+        assert!(events.len() == 3);
+        let listener = events.iter().find(|e| e["cat"] == "listener").unwrap();
+        assert!(listener["args"]["count"] == 2);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::Pearl;
+
+use super::{IterMut, Link, Links, PearlView, WorldQueue};
+
+/// A view over every pearl of type `P` stored in a world.
+///
+/// This is passed to [`BatchListener`](crate::pearl::BatchListener) so that
+/// all pearls of a type can be processed as one tightly packed slice.
+pub struct BatchView<'a, 'world, P: Pearl> {
+    world: &'a mut WorldQueue<'world>,
+    _type: std::marker::PhantomData<P>,
+}
+
+impl<'a, 'world, P: Pearl> BatchView<'a, 'world, P> {
+    pub fn new(world: &'a mut WorldQueue<'world>) -> Self {
+        Self {
+            world,
+            _type: std::marker::PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.world.len::<P>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the links for every pearl, in the same order as [`pearls`](Self::pearls).
+    pub fn links(&self) -> Links<'_, P> {
+        self.world.links()
+    }
+
+    pub fn pearls(&self) -> &[P] {
+        self.world.slice()
+    }
+
+    pub fn pearls_mut(&mut self) -> &mut [P] {
+        self.world.slice_mut()
+    }
+
+    /// Returns the links for every pearl along with the pearls as a mutable slice,
+    /// so that both can be used at the same time.
+    pub fn split(&mut self) -> (Links<'_, P>, &mut [P]) {
+        self.world.split_slice_mut()
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, P> {
+        self.world.iter_mut()
+    }
+
+    pub fn world(&self) -> &WorldQueue<'world> {
+        self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut WorldQueue<'world> {
+        self.world
+    }
+
+    pub fn get_view<'b, P2: Pearl>(
+        &'b mut self,
+        link: Link<P2>,
+    ) -> Option<PearlView<'b, 'world, P2>> {
+        PearlView::new(link, self.world)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        pearl::{BatchListener, EventSource},
+        world::Link,
+        Pearl, World,
+    };
+
+    use super::BatchView;
+
+    struct Tick;
+
+    struct Particle(u32);
+
+    impl Pearl for Particle {
+        fn register(source: &mut impl EventSource<Self>) {
+            source.listen_batch::<Tick>();
+            source.listen_batch::<Mark>();
+        }
+    }
+
+    impl BatchListener<Tick> for Particle {
+        fn trigger_all(mut pearls: BatchView<Self>, _: &mut Tick) {
+            for particle in pearls.pearls_mut() {
+                particle.0 += 1;
+            }
+        }
+    }
+
+    struct Mark(Link<Particle>);
+
+    impl BatchListener<Mark> for Particle {
+        fn trigger_all(mut pearls: BatchView<Self>, mark: &mut Mark) {
+            let (links, pearls) = pearls.split();
+            for (link, particle) in links.zip(pearls) {
+                if link == mark.0 {
+                    particle.0 += 100;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn trigger_all() {
+        let mut world = World::new();
+        let p1 = world.insert(Particle(0));
+        let p2 = world.insert(Particle(10));
+
+        world.trigger(&mut Tick);
+        world.trigger(&mut Tick);
+
+        assert!(world.get(p1).unwrap().0 == 2);
+        assert!(world.get(p2).unwrap().0 == 12);
+
+        world.trigger(&mut Mark(p2));
+        assert!(world.get(p1).unwrap().0 == 2);
+        assert!(world.get(p2).unwrap().0 == 112);
+    }
+}
@@ -0,0 +1,105 @@
+use hashbrown::HashMap;
+use indexmap::IndexSet;
+
+use super::Link;
+
+/// The name and tag index for a single pearl type.
+#[derive(Clone, Default)]
+pub(crate) struct Labels {
+    names: HashMap<String, Link<()>>,
+    link_names: HashMap<Link<()>, String>,
+    tags: HashMap<String, IndexSet<Link<()>>>,
+    link_tags: HashMap<Link<()>, IndexSet<String>>,
+}
+
+impl Labels {
+    pub fn name(&self, link: Link<()>) -> Option<&str> {
+        self.link_names.get(&link).map(|name| name.as_str())
+    }
+
+    pub fn find(&self, name: &str) -> Option<Link<()>> {
+        self.names.get(name).copied()
+    }
+
+    pub fn set_name(&mut self, link: Link<()>, name: String) {
+        self.clear_name(link);
+
+        // names are unique, so remove the name from its previous owner
+        if let Some(old_link) = self.names.insert(name.clone(), link) {
+            self.link_names.remove(&old_link);
+        }
+
+        self.link_names.insert(link, name);
+    }
+
+    pub fn clear_name(&mut self, link: Link<()>) -> Option<String> {
+        let name = self.link_names.remove(&link)?;
+        self.names.remove(&name);
+        Some(name)
+    }
+
+    pub fn has_tag(&self, link: Link<()>, tag: &str) -> bool {
+        match self.tags.get(tag) {
+            Some(links) => links.contains(&link),
+            None => false,
+        }
+    }
+
+    pub fn tagged(&self, tag: &str) -> impl Iterator<Item = Link<()>> + '_ {
+        self.tags.get(tag).into_iter().flatten().copied()
+    }
+
+    pub fn tags(&self, link: Link<()>) -> impl Iterator<Item = &str> + '_ {
+        let tags = self.link_tags.get(&link).into_iter().flatten();
+        tags.map(|tag| tag.as_str())
+    }
+
+    pub fn add_tag(&mut self, link: Link<()>, tag: String) -> bool {
+        let links = self.tags.entry(tag.clone()).or_default();
+        if !links.insert(link) {
+            return false;
+        }
+
+        self.link_tags.entry(link).or_default().insert(tag);
+        true
+    }
+
+    pub fn remove_tag(&mut self, link: Link<()>, tag: &str) -> bool {
+        let Some(links) = self.tags.get_mut(tag) else {
+            return false;
+        };
+
+        if !links.swap_remove(&link) {
+            return false;
+        }
+
+        if links.is_empty() {
+            self.tags.remove(tag);
+        }
+
+        let link_tags = self.link_tags.get_mut(&link).unwrap();
+        link_tags.swap_remove(tag);
+        if link_tags.is_empty() {
+            self.link_tags.remove(&link);
+        }
+
+        true
+    }
+
+    /// Removes all names and tags associated with `link`.
+    /// Returns `true` if `link` has a name or any tags.
+    pub fn has_labels(&self, link: Link<()>) -> bool {
+        self.link_names.contains_key(&link) || self.link_tags.contains_key(&link)
+    }
+
+    pub fn remove(&mut self, link: Link<()>) {
+        self.clear_name(link);
+        for tag in self.link_tags.remove(&link).into_iter().flatten() {
+            let links = self.tags.get_mut(&tag).unwrap();
+            links.swap_remove(&link);
+            if links.is_empty() {
+                self.tags.remove(&tag);
+            }
+        }
+    }
+}
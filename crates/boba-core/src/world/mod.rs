@@ -1,8 +1,17 @@
+#[allow(clippy::module_inception)]
 mod world;
 
+mod labels;
+
+pub mod batch;
 pub mod queue;
+pub mod send;
+pub mod stats;
 pub mod view;
 
+pub use batch::BatchView;
 pub use queue::WorldQueue;
+pub use send::SendWorld;
+pub use stats::WorldStats;
 pub use view::PearlView;
 pub use world::*;
@@ -1,8 +1,20 @@
+use std::{iter::Sum, ops::ControlFlow};
+
 use indexmap::IndexSet;
 
-use crate::{pearl::Event, Pearl, World};
+use crate::{
+    pearl::{Event, EventFamily, Listener, Owned, Poolable},
+    prefab::{Prefab, PrefabLinks},
+    query::{Query, QueryEvent, QueryFamily},
+    Pearl, World,
+};
+
+use super::{
+    stats::{PendingStats, WorldStats},
+    AccessError, Iter, IterMut, Link, Links, LinksCopied, PearlView, Pearls, PearlsMut,
+};
 
-use super::{Iter, IterMut, Link, Links, LinksCopied, PearlView, Pearls, PearlsMut};
+type Deferred = Box<dyn FnOnce(&mut World)>;
 
 /// A wrapper around a [`World`] that preserves all current links connections.
 ///
@@ -10,7 +22,7 @@ use super::{Iter, IterMut, Link, Links, LinksCopied, PearlView, Pearls, PearlsMu
 /// This means that any queued operations (such as removals) will be deferred.
 pub struct WorldQueue<'a> {
     pub(crate) world: &'a mut World,
-    queue: Vec<Box<dyn FnOnce(&mut World)>>,
+    queue: Vec<Deferred>,
     destroy: IndexSet<Link<()>>,
 }
 
@@ -60,6 +72,21 @@ impl<'a> WorldQueue<'a> {
         self.world.get_mut(link)
     }
 
+    pub fn get_many_mut<P: Pearl, const N: usize>(
+        &mut self,
+        links: [Link<P>; N],
+    ) -> Result<[&mut P; N], AccessError> {
+        self.world.get_many_mut(links)
+    }
+
+    pub fn get_pair_mut<A: Pearl, B: Pearl>(
+        &mut self,
+        a: Link<A>,
+        b: Link<B>,
+    ) -> Result<(&mut A, &mut B), AccessError> {
+        self.world.get_pair_mut(a, b)
+    }
+
     pub fn insert<P: Pearl>(&mut self, pearl: P) -> Link<P> {
         self.world.insert(pearl)
     }
@@ -68,7 +95,19 @@ impl<'a> WorldQueue<'a> {
         self.world.insert_then(pearl, then)
     }
 
-    pub fn links<P: Pearl>(&self) -> Links<P> {
+    pub fn pooled<P: Poolable>(&self) -> usize {
+        self.world.pooled::<P>()
+    }
+
+    pub fn spawn_pooled<P: Poolable>(&mut self, init: impl FnOnce(&mut P)) -> Link<P> {
+        self.world.spawn_pooled(init)
+    }
+
+    pub fn spawn_prefab(&mut self, prefab: &Prefab) -> PrefabLinks {
+        self.world.spawn_prefab(prefab)
+    }
+
+    pub fn links<P: Pearl>(&self) -> Links<'_, P> {
         self.world.links()
     }
 
@@ -76,30 +115,179 @@ impl<'a> WorldQueue<'a> {
         self.world.links_copied()
     }
 
-    pub fn pearls<P: Pearl>(&self) -> Pearls<P> {
+    pub fn pearls<P: Pearl>(&self) -> Pearls<'_, P> {
         self.world.pearls()
     }
 
-    pub fn pearls_mut<P: Pearl>(&mut self) -> PearlsMut<P> {
+    pub fn pearls_mut<P: Pearl>(&mut self) -> PearlsMut<'_, P> {
         self.world.pearls_mut()
     }
 
-    pub fn iter<P: Pearl>(&self) -> Iter<P> {
+    pub fn slice<P: Pearl>(&self) -> &[P] {
+        self.world.slice()
+    }
+
+    pub fn slice_mut<P: Pearl>(&mut self) -> &mut [P] {
+        self.world.slice_mut()
+    }
+
+    pub fn split_slice_mut<P: Pearl>(&mut self) -> (Links<'_, P>, &mut [P]) {
+        self.world.split_slice_mut()
+    }
+
+    pub fn iter<P: Pearl>(&self) -> Iter<'_, P> {
         self.world.iter()
     }
 
-    pub fn iter_mut<P: Pearl>(&mut self) -> IterMut<P> {
+    pub fn iter_mut<P: Pearl>(&mut self) -> IterMut<'_, P> {
         self.world.iter_mut()
     }
 
+    pub fn name<P: Pearl>(&self, link: Link<P>) -> Option<&str> {
+        self.world.name(link)
+    }
+
+    pub fn find<P: Pearl>(&self, name: &str) -> Option<Link<P>> {
+        self.world.find(name)
+    }
+
+    pub fn set_name<P: Pearl>(&mut self, link: Link<P>, name: impl Into<String>) -> bool {
+        self.world.set_name(link, name)
+    }
+
+    pub fn clear_name<P: Pearl>(&mut self, link: Link<P>) -> Option<String> {
+        self.world.clear_name(link)
+    }
+
+    pub fn has_tag<P: Pearl>(&self, link: Link<P>, tag: &str) -> bool {
+        self.world.has_tag(link, tag)
+    }
+
+    pub fn add_tag<P: Pearl>(&mut self, link: Link<P>, tag: impl Into<String>) -> bool {
+        self.world.add_tag(link, tag)
+    }
+
+    pub fn remove_tag<P: Pearl>(&mut self, link: Link<P>, tag: &str) -> bool {
+        self.world.remove_tag(link, tag)
+    }
+
+    pub fn links_with_tag<P: Pearl>(&self, tag: &str) -> LinksCopied<P> {
+        self.world.links_with_tag(tag)
+    }
+
+    pub fn subscribe<E: Event, P: Listener<E>>(&mut self, link: Link<P>) -> bool {
+        self.world.subscribe::<E, P>(link)
+    }
+
+    pub fn unsubscribe<E: Event, P: Listener<E>>(&mut self, link: Link<P>) -> bool {
+        self.world.unsubscribe::<E, P>(link)
+    }
+
+    pub fn is_subscribed<E: Event, P: Listener<E>>(&self, link: Link<P>) -> bool {
+        self.world.is_subscribed::<E, P>(link)
+    }
+
     pub fn trigger<E: Event>(&mut self, data: &mut E) {
-        World::trigger_nested::<E>(self, data);
+        World::trigger_nested::<Owned<E>>(self, data);
+    }
+
+    pub fn trigger_family<F: EventFamily>(&mut self, data: &mut F::Event<'_>) {
+        World::trigger_nested::<F>(self, data);
+    }
+
+    pub fn query_event<Q: Query>(&mut self, query: &Q) -> Vec<Q::Response> {
+        self.query_fold(query, Vec::new(), |mut responses, response| {
+            responses.push(response);
+            ControlFlow::Continue(responses)
+        })
+    }
+
+    pub fn query_fold<Q: Query, A>(
+        &mut self,
+        query: &Q,
+        init: A,
+        mut f: impl FnMut(A, Q::Response) -> ControlFlow<A, A>,
+    ) -> A {
+        let mut acc = Some(init);
+        let mut reducer = |response| match f(acc.take().unwrap(), response) {
+            ControlFlow::Continue(next) => {
+                acc = Some(next);
+                ControlFlow::Continue(())
+            }
+            ControlFlow::Break(next) => {
+                acc = Some(next);
+                ControlFlow::Break(())
+            }
+        };
+
+        let mut event = QueryEvent::new(query, &mut reducer);
+        World::trigger_nested::<QueryFamily<Q>>(self, &mut event);
+        acc.unwrap()
+    }
+
+    pub fn query_first<Q: Query>(&mut self, query: &Q) -> Option<Q::Response> {
+        self.query_fold(query, None, |_, response| {
+            ControlFlow::Break(Some(response))
+        })
+    }
+
+    pub fn query_any<Q: Query<Response = bool>>(&mut self, query: &Q) -> bool {
+        self.query_fold(query, false, |_, response| match response {
+            true => ControlFlow::Break(true),
+            false => ControlFlow::Continue(false),
+        })
     }
 
+    pub fn query_sum<Q: Query>(&mut self, query: &Q) -> Q::Response
+    where
+        Q::Response: Sum,
+    {
+        self.query_event(query).into_iter().sum()
+    }
+
+    /// Returns a report of the world, including the operations waiting in this queue.
+    pub fn stats(&self) -> WorldStats {
+        let mut stats = self.world.stats();
+        stats.pending = PendingStats {
+            operations: self.queue.len(),
+            removals: self.destroy.len(),
+        };
+        stats
+    }
+
+    /// Queues `f` to run on the world when this queue is dropped.
+    ///
+    /// Deferred functions always run on the thread that created the queue before it is dropped,
+    /// so they are not required to be [`Send`].
+    /// Only a [`SendWorld`](super::SendWorld) is sent between threads, and it has no queue.
     pub fn defer(&mut self, f: impl FnOnce(&mut World) + 'static) {
         self.queue.push(Box::new(f));
     }
 
+    /// Queues the pearl at `link` to be recycled into its pool.
+    ///
+    /// See [`World::recycle`] for more details.
+    pub fn recycle<P: Poolable>(&mut self, link: Link<P>) -> bool {
+        if !self.world.contains(link) || !self.destroy.insert(link.into_type()) {
+            return false;
+        }
+
+        self.defer(move |world| {
+            world.recycle(link);
+        });
+
+        true
+    }
+
+    /// Queues every pearl of type `P` to be unregistered.
+    ///
+    /// See [`World::unregister`] for more details.
+    pub fn unregister<P: Pearl>(&mut self) {
+        self.defer(|world| {
+            world.unregister::<P>();
+        });
+    }
+
     pub fn destroy<P: Pearl>(&mut self, link: Link<P>) -> bool {
         // fail if the pearl does not exist
         if !self.world.contains(link) {
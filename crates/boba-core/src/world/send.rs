@@ -0,0 +1,213 @@
+use std::any::{Any, TypeId};
+
+use handle_map::{map::DenseHandleMap, map::SparseHandleMap, Handle};
+use hashbrown::HashMap;
+
+use crate::Pearl;
+
+use super::{
+    world::{transferred, MovedLinks},
+    Link, LinkRemap, World, WorldQueue,
+};
+
+type TransferredFn = fn(&mut WorldQueue, &LinkRemap, &MovedLinks);
+
+struct SendMap {
+    map: Box<dyn Any + Send>,
+    len: fn(&(dyn Any + Send)) -> usize,
+    merge: fn(Box<dyn Any + Send>, Handle<SendMap>, &mut World, &mut LinkRemap) -> MovedLinks,
+    transferred: TransferredFn,
+}
+
+impl SendMap {
+    fn new<P: Pearl + Send>() -> Self {
+        Self {
+            map: Box::new(DenseHandleMap::<P>::new()),
+            len: |map| map.downcast_ref::<DenseHandleMap<P>>().unwrap().len(),
+            merge: |map, map_handle, world, remap| {
+                let map = *map.downcast::<DenseHandleMap<P>>().unwrap();
+                let mut moved = Vec::with_capacity(map.len());
+                for (pearl_handle, pearl) in map {
+                    let old_link = Link::<P>::from_raw(map_handle.id(), pearl_handle.id());
+                    let new_link = world.insert_quiet(pearl);
+                    remap.insert(old_link, new_link);
+                    moved.push((old_link.into_type(), new_link.into_type()));
+                }
+                moved
+            },
+            transferred: transferred::<P>,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        (self.len)(self.map.as_ref()) == 0
+    }
+
+    fn get<P: Pearl>(&self) -> &DenseHandleMap<P> {
+        self.map.downcast_ref().unwrap()
+    }
+
+    fn get_mut<P: Pearl>(&mut self) -> &mut DenseHandleMap<P> {
+        self.map.downcast_mut().unwrap()
+    }
+}
+
+/// A storage for pearls that can be sent to another thread.
+///
+/// Only pearl types that are [`Send`] may be stored in a `SendWorld`,
+/// and their storage is [`Send`] as well, so the whole world can be sent.
+/// This is used to build pearls on a loading thread,
+/// or to hand them to a dedicated simulation thread.
+///
+/// A `SendWorld` is only storage, so no [`Pearl`] hooks or listeners run while pearls are in it.
+/// Once it reaches the thread that will run it, it is moved into a [`World`]
+/// using [`World::merge_send`] or [`into_world`](Self::into_world).
+#[derive(Default)]
+pub struct SendWorld {
+    type_maps: HashMap<TypeId, Handle<SendMap>>,
+    maps: SparseHandleMap<SendMap>,
+}
+
+impl SendWorld {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn types(&self) -> usize {
+        self.maps.iter().filter(|(_, map)| !map.is_empty()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types() == 0
+    }
+
+    pub fn len<P: Pearl>(&self) -> usize {
+        self.map::<P>().map_or(0, |(_, map)| map.len())
+    }
+
+    pub fn contains<P: Pearl>(&self, link: Link<P>) -> bool {
+        self.get(link).is_some()
+    }
+
+    pub fn get<P: Pearl>(&self, link: Link<P>) -> Option<&P> {
+        let (map_handle, map) = self.map::<P>()?;
+        let pearl_handle = Self::pearl_handle(map_handle, link)?;
+        map.get(pearl_handle)
+    }
+
+    pub fn get_mut<P: Pearl>(&mut self, link: Link<P>) -> Option<&mut P> {
+        let map_handle = *self.type_maps.get(&TypeId::of::<P>())?;
+        let pearl_handle = Self::pearl_handle(map_handle, link)?;
+        let map = self.maps.get_mut(map_handle).unwrap();
+        map.get_mut::<P>().get_mut(pearl_handle)
+    }
+
+    pub fn insert<P: Pearl + Send>(&mut self, pearl: P) -> Link<P> {
+        let maps = &mut self.maps;
+        let map_handle = *self
+            .type_maps
+            .entry(TypeId::of::<P>())
+            .or_insert_with(|| maps.insert(SendMap::new::<P>()));
+
+        let map = self.maps.get_mut(map_handle).unwrap();
+        let pearl_handle = map.get_mut::<P>().insert(pearl);
+        Link::from_raw(map_handle.id(), pearl_handle.id())
+    }
+
+    pub fn remove<P: Pearl>(&mut self, link: Link<P>) -> Option<P> {
+        let map_handle = *self.type_maps.get(&TypeId::of::<P>())?;
+        let pearl_handle = Self::pearl_handle(map_handle, link)?;
+        let map = self.maps.get_mut(map_handle).unwrap();
+        map.get_mut::<P>().remove(pearl_handle)
+    }
+
+    /// Moves every pearl into a new [`World`],
+    /// and returns it with a [`LinkRemap`] from their old links to their new links.
+    ///
+    /// See [`World::merge_send`] for more details.
+    pub fn into_world(self) -> (World, LinkRemap) {
+        let mut world = World::new();
+        let remap = world.merge_send(self);
+        (world, remap)
+    }
+
+    /// Moves every pearl into `world` without calling any [`Pearl`] hooks,
+    /// and returns the functions that call [`Pearl::on_transfer`] for each type that was moved.
+    pub(super) fn move_into(
+        mut self,
+        world: &mut World,
+        remap: &mut LinkRemap,
+    ) -> Vec<(TransferredFn, MovedLinks)> {
+        let mut moved = Vec::new();
+        for (_, map_handle) in self.type_maps.drain() {
+            let map = self.maps.remove(map_handle).unwrap();
+            let links = (map.merge)(map.map, map_handle, world, remap);
+            moved.push((map.transferred, links));
+        }
+        moved
+    }
+
+    fn map<P: Pearl>(&self) -> Option<(Handle<SendMap>, &DenseHandleMap<P>)> {
+        let map_handle = *self.type_maps.get(&TypeId::of::<P>())?;
+        Some((map_handle, self.maps.get(map_handle).unwrap().get::<P>()))
+    }
+
+    /// Returns the handle of the pearl at `link` if it was created by the map at `map_handle`.
+    fn pearl_handle<P>(map_handle: Handle<SendMap>, link: Link<P>) -> Option<Handle<P>> {
+        let pearl_handle = Handle::from_raw(link.id());
+        match Link::from_raw(map_handle.id(), link.id()) == link {
+            true => Some(pearl_handle),
+            false => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::world::Transferred;
+
+    use super::*;
+
+    struct Body(u32);
+
+    impl Pearl for Body {}
+
+    struct Joint {
+        body: Link<Body>,
+    }
+
+    impl Pearl for Joint {
+        fn on_transfer(mut pearl: Transferred<Self>) {
+            let remap = pearl.remap();
+            assert!(remap.remap(&mut pearl.body));
+        }
+    }
+
+    #[test]
+    fn send_to_thread() {
+        let mut world = SendWorld::new();
+        let body = world.insert(Body(0));
+        world.insert(Joint { body });
+        assert!(world.types() == 2);
+
+        // build more pearls on another thread
+        let world = std::thread::spawn(move || {
+            world.get_mut(body).unwrap().0 += 1;
+            let other = world.insert(Body(5));
+            assert!(world.remove(other).unwrap().0 == 5);
+            world
+        });
+
+        let world = world.join().unwrap();
+        assert!(world.len::<Body>() == 1);
+        assert!(world.len::<Joint>() == 1);
+
+        // links between the moved pearls are remapped when they reach a world
+        let (world, remap) = world.into_world();
+        let new_body = remap.get(body).unwrap();
+        assert!(world.get(new_body).unwrap().0 == 1);
+        assert!(world
+            .iter::<Joint>()
+            .all(|(_, joint)| joint.body == new_body));
+    }
+}
@@ -0,0 +1,147 @@
+use std::fmt::Display;
+
+use crate::{
+    pearl::{Event, EventFamily, Owned},
+    Pearl,
+};
+
+/// The statistics for a single pearl type stored in a world.
+#[derive(Debug, Clone)]
+pub struct PearlStats {
+    pub name: &'static str,
+    pub count: usize,
+    pub capacity: usize,
+    /// The bytes allocated for the pearl storage,
+    /// not including any heap memory owned by the pearls themselves.
+    pub bytes: usize,
+    /// The names of the events this type listens to.
+    pub events: Vec<&'static str>,
+}
+
+/// The statistics for a single event type registered in a world.
+#[derive(Debug, Clone)]
+pub struct EventStats {
+    pub name: &'static str,
+    /// The names of the pearl types listening to this event, in dispatch order.
+    pub listeners: Vec<&'static str>,
+}
+
+/// The operations waiting in a [`WorldQueue`](super::WorldQueue).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PendingStats {
+    /// The number of queued operations, including removals.
+    pub operations: usize,
+    /// The number of pearls queued to be removed.
+    pub removals: usize,
+}
+
+/// A report of everything stored in a world, created using [`World::stats`](super::World::stats).
+///
+/// Pearl and event types are sorted by name.
+#[derive(Debug, Clone, Default)]
+pub struct WorldStats {
+    pub pearls: Vec<PearlStats>,
+    pub events: Vec<EventStats>,
+    pub pending: PendingStats,
+}
+
+impl WorldStats {
+    /// Returns the stats for the pearl type `P`.
+    pub fn pearl<P: Pearl>(&self) -> Option<&PearlStats> {
+        let name = std::any::type_name::<P>();
+        self.pearls.iter().find(|stats| stats.name == name)
+    }
+
+    /// Returns the number of pearls of type `P`.
+    pub fn count<P: Pearl>(&self) -> usize {
+        self.pearl::<P>().map_or(0, |stats| stats.count)
+    }
+
+    /// Returns the stats for the event `E`.
+    pub fn event<E: Event>(&self) -> Option<&EventStats> {
+        self.family::<Owned<E>>()
+    }
+
+    /// Returns the stats for the [`EventFamily`] `F`.
+    pub fn family<F: EventFamily>(&self) -> Option<&EventStats> {
+        let name = F::name();
+        self.events.iter().find(|stats| stats.name == name)
+    }
+
+    /// Returns the total bytes allocated for all pearl storage.
+    pub fn total_bytes(&self) -> usize {
+        self.pearls.iter().map(|stats| stats.bytes).sum()
+    }
+}
+
+impl Display for WorldStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "pearls ({} bytes):", self.total_bytes())?;
+        for stats in self.pearls.iter() {
+            writeln!(
+                f,
+                "  {}: {}/{} ({} bytes)",
+                stats.name, stats.count, stats.capacity, stats.bytes
+            )?;
+        }
+
+        writeln!(f, "events:")?;
+        for stats in self.events.iter() {
+            writeln!(f, "  {}: [{}]", stats.name, stats.listeners.join(", "))?;
+        }
+
+        write!(
+            f,
+            "pending: {} operations, {} removals",
+            self.pending.operations, self.pending.removals
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        pearl::{EventSource, Listener},
+        world::PearlView,
+        World,
+    };
+
+    use super::*;
+
+    struct Tick;
+
+    struct Clock;
+
+    impl Pearl for Clock {
+        fn register(source: &mut impl EventSource<Self>) {
+            source.listen::<Tick>();
+        }
+    }
+
+    impl Listener<Tick> for Clock {
+        fn trigger(mut pearl: PearlView<Self>, _: &mut Tick) {
+            let pending = pearl.world().stats().pending;
+            pearl.destroy_self();
+            let stats = pearl.world().stats();
+            assert!(stats.pending.removals == pending.removals + 1);
+        }
+    }
+
+    #[test]
+    fn report() {
+        let mut world = World::new();
+        world.insert(Clock);
+        world.insert(Clock);
+
+        let stats = world.stats();
+        let clock = stats.pearl::<Clock>().unwrap();
+        assert!(clock.count == 2);
+        assert!(clock.capacity >= 2);
+        assert!(clock.events == [std::any::type_name::<Tick>()]);
+        let tick = stats.event::<Tick>().unwrap();
+        assert!(tick.listeners == [std::any::type_name::<Clock>()]);
+
+        world.trigger(&mut Tick);
+        assert!(world.stats().count::<Clock>() == 0);
+    }
+}
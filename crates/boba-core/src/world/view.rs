@@ -1,8 +1,11 @@
 use std::ops::{Deref, DerefMut};
 
-use crate::Pearl;
+use crate::{
+    pearl::{Event, Listener, Poolable},
+    Pearl,
+};
 
-use super::{Link, WorldQueue};
+use super::{AccessError, Link, WorldQueue};
 
 pub struct PearlView<'a, 'world, P: Pearl> {
     world: &'a mut WorldQueue<'world>,
@@ -41,11 +44,11 @@ impl<'a, 'world, P: Pearl> PearlView<'a, 'world, P> {
     }
 
     pub fn world(&self) -> &WorldQueue<'world> {
-        &self.world
+        self.world
     }
 
     pub fn world_mut(&mut self) -> &mut WorldQueue<'world> {
-        &mut self.world
+        self.world
     }
 
     pub fn get_view<'b, P2: Pearl>(
@@ -55,7 +58,74 @@ impl<'a, 'world, P: Pearl> PearlView<'a, 'world, P> {
         PearlView::new(link, self.world)
     }
 
+    pub fn get_many_mut<P2: Pearl, const N: usize>(
+        &mut self,
+        links: [Link<P2>; N],
+    ) -> Result<[&mut P2; N], AccessError> {
+        self.world.get_many_mut(links)
+    }
+
+    pub fn get_pair_mut<A: Pearl, B: Pearl>(
+        &mut self,
+        a: Link<A>,
+        b: Link<B>,
+    ) -> Result<(&mut A, &mut B), AccessError> {
+        self.world.get_pair_mut(a, b)
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.world.name(self.link)
+    }
+
+    pub fn set_name(&mut self, name: impl Into<String>) -> bool {
+        self.world.set_name(self.link, name)
+    }
+
+    pub fn clear_name(&mut self) -> Option<String> {
+        self.world.clear_name(self.link)
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.world.has_tag(self.link, tag)
+    }
+
+    pub fn add_tag(&mut self, tag: impl Into<String>) -> bool {
+        self.world.add_tag(self.link, tag)
+    }
+
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        self.world.remove_tag(self.link, tag)
+    }
+
+    pub fn subscribe<E: Event>(&mut self) -> bool
+    where
+        P: Listener<E>,
+    {
+        self.world.subscribe::<E, P>(self.link)
+    }
+
+    pub fn unsubscribe<E: Event>(&mut self) -> bool
+    where
+        P: Listener<E>,
+    {
+        self.world.unsubscribe::<E, P>(self.link)
+    }
+
+    pub fn is_subscribed<E: Event>(&self) -> bool
+    where
+        P: Listener<E>,
+    {
+        self.world.is_subscribed::<E, P>(self.link)
+    }
+
     pub fn destroy_self(&mut self) -> bool {
         self.world.destroy(self.link)
     }
+
+    pub fn recycle_self(&mut self) -> bool
+    where
+        P: Poolable,
+    {
+        self.world.recycle(self.link)
+    }
 }
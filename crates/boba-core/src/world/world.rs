@@ -2,7 +2,9 @@ use std::{
     any::{Any, TypeId},
     fmt::{Debug, Display},
     hash::Hash,
-    ops::{Deref, DerefMut},
+    iter::Sum,
+    ops::{ControlFlow, Deref, DerefMut},
+    sync::{Arc, Weak},
 };
 
 use handle_map::{
@@ -10,24 +12,31 @@ use handle_map::{
     Handle,
 };
 use hashbrown::HashMap;
-use indexmap::IndexMap;
-
-use crate::{pearl::Event, world::WorldQueue, Pearl};
+use indexmap::{IndexMap, IndexSet};
+
+use crate::{
+    pearl::{Event, EventFamily, FamilyListener, Listener, Owned, Poolable},
+    prefab::{Prefab, PrefabLinks},
+    query::Query,
+    world::WorldQueue,
+    Pearl,
+};
 
-use super::PearlView;
+use super::{
+    labels::Labels,
+    stats::{EventStats, PearlStats, PendingStats, WorldStats},
+    BatchView, PearlView, SendWorld,
+};
 
 pub struct Link<P> {
-    map_handle: Handle<Box<dyn Any>>,
+    map_handle: Handle<AnyMap>,
     pearl_handle: Handle<P>,
 }
 
 impl<P> Copy for Link<P> {}
 impl<P> Clone for Link<P> {
     fn clone(&self) -> Self {
-        Self {
-            map_handle: self.map_handle.clone(),
-            pearl_handle: self.pearl_handle.clone(),
-        }
+        *self
     }
 }
 
@@ -62,6 +71,21 @@ impl<P> Display for Link<P> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<P> serde::Serialize for Link<P> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.map_handle.id(), self.pearl_handle.id()).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, P> serde::Deserialize<'de> for Link<P> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (map_id, pearl_id) = <(u64, u64)>::deserialize(deserializer)?;
+        Ok(Link::from_raw(map_id, pearl_id))
+    }
+}
+
 impl<P> Link<P> {
     pub fn id(&self) -> u64 {
         self.pearl_handle.id()
@@ -84,29 +108,252 @@ impl<P> Link<P> {
     }
 }
 
+/// The error returned when multiple pearls could not be accessed at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessError {
+    /// One of the links does not point to a valid pearl.
+    InvalidLink,
+    /// Two of the links point to the same pearl.
+    Aliased,
+}
+
+impl Display for AccessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLink => write!(f, "link does not point to a valid pearl"),
+            Self::Aliased => write!(f, "multiple links point to the same pearl"),
+        }
+    }
+}
+
+impl std::error::Error for AccessError {}
+
+/// The type erased storage for a single pearl type.
+///
+/// This tracks whether the storage may have changed since the last snapshot.
+struct AnyMap {
+    map: Box<dyn Any>,
+    len: fn(&dyn Any) -> usize,
+    changed: bool,
+}
+
+impl AnyMap {
+    pub fn new<P: Pearl>(map: DenseHandleMap<P>) -> Self {
+        Self {
+            map: Box::new(map),
+            len: |map| map.downcast_ref::<DenseHandleMap<P>>().unwrap().len(),
+            changed: true,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (self.len)(self.map.as_ref()) == 0
+    }
+
+    pub fn get<P: Pearl>(&self) -> &DenseHandleMap<P> {
+        self.map.downcast_ref().unwrap()
+    }
+
+    pub fn get_mut<P: Pearl>(&mut self) -> &mut DenseHandleMap<P> {
+        self.changed = true;
+        self.map.downcast_mut().unwrap()
+    }
+}
+
+type SnapshotMap = Arc<dyn Any + Send + Sync>;
+type Subscriptions = HashMap<TypeId, IndexSet<Link<()>>>;
+
+struct SnapshotData {
+    clone: fn(&AnyMap) -> SnapshotMap,
+    restore: fn(&mut AnyMap, &SnapshotMap),
+    empty: fn(&mut AnyMap),
+    /// The storage shared with the latest snapshot, for as long as any snapshot holds it.
+    last: Option<Weak<dyn Any + Send + Sync>>,
+}
+
+#[derive(Clone)]
+struct SnapshotEntry {
+    map: SnapshotMap,
+    subscriptions: Arc<Subscriptions>,
+    labels: Arc<Labels>,
+}
+
+/// A snapshot of all pearl types that opted into snapshots,
+/// created using [`World::snapshot`].
+///
+/// Cloning a snapshot is cheap, as the underlying storage is shared.
+/// Snapshots only hold pearl types that are [`Send`] and [`Sync`],
+/// so they can be shared with other threads.
+#[derive(Clone, Default)]
+pub struct WorldSnapshot {
+    entries: HashMap<TypeId, SnapshotEntry>,
+}
+
+impl WorldSnapshot {
+    /// Returns the number of pearl types stored in this snapshot.
+    pub fn types(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+struct Pool<P> {
+    values: Vec<P>,
+    capacity: usize,
+}
+
+pub(super) type MovedLinks = Vec<(Link<()>, Link<()>)>;
+
+/// The type erased functions used to move a whole pearl map into another world.
+struct MergeFns {
+    insert: fn(&mut World, AnyMap, Handle<AnyMap>, &mut LinkRemap) -> MovedLinks,
+    transferred: fn(&mut WorldQueue, &LinkRemap, &MovedLinks),
+}
+
+struct MapUsage {
+    count: usize,
+    capacity: usize,
+    bytes: usize,
+}
+
+/// The names, tags and subscriptions of a single pearl, carried along when it is moved.
+struct CarriedLabels {
+    name: Option<String>,
+    tags: Vec<String>,
+    subscriptions: Vec<TypeId>,
+}
+
 struct MapData {
-    handle: Handle<Box<dyn Any>>,
+    name: &'static str,
+    usage: fn(&AnyMap) -> MapUsage,
+    handle: Handle<AnyMap>,
     events: IndexMap<TypeId, fn(&mut World)>,
+    /// Shared with snapshots, and cloned on write.
+    subscriptions: Arc<Subscriptions>,
+    /// Shared with snapshots, and cloned on write.
+    labels: Arc<Labels>,
+    snapshot: Option<SnapshotData>,
+    merge: MergeFns,
 }
 
 impl MapData {
-    pub fn new(handle: Handle<Box<dyn Any>>) -> Self {
+    pub fn new<P: Pearl>(handle: Handle<AnyMap>) -> Self {
         Self {
+            name: std::any::type_name::<P>(),
+            usage: |anymap| {
+                let map = anymap.get::<P>();
+                MapUsage {
+                    count: map.len(),
+                    capacity: map.capacity(),
+                    bytes: map.allocated_bytes(),
+                }
+            },
             handle,
             events: IndexMap::new(),
+            subscriptions: Arc::default(),
+            labels: Arc::default(),
+            snapshot: None,
+            merge: MergeFns {
+                insert: |world, anymap, map_handle, remap| {
+                    let map = *anymap.map.downcast::<DenseHandleMap<P>>().unwrap();
+                    let mut moved = Vec::with_capacity(map.len());
+                    for (pearl_handle, pearl) in map {
+                        let old_link = Link::<P> {
+                            map_handle,
+                            pearl_handle,
+                        };
+                        let new_link = world.insert_quiet(pearl);
+                        remap.insert(old_link, new_link);
+                        moved.push((old_link.into_type(), new_link.into_type()));
+                    }
+                    moved
+                },
+                transferred: transferred::<P>,
+            },
+        }
+    }
+
+    fn carried_labels(&self, link: Link<()>) -> CarriedLabels {
+        let subscriptions = self.subscriptions.iter();
+        CarriedLabels {
+            name: self.labels.name(link).map(String::from),
+            tags: self.labels.tags(link).map(String::from).collect(),
+            subscriptions: subscriptions
+                .filter(|(_, links)| links.contains(&link))
+                .map(|(event_id, _)| *event_id)
+                .collect(),
+        }
+    }
+
+    fn apply_labels(&mut self, link: Link<()>, carried: CarriedLabels) {
+        let labels = Arc::make_mut(&mut self.labels);
+        if let Some(name) = carried.name {
+            labels.set_name(link, name);
+        }
+
+        for tag in carried.tags {
+            labels.add_tag(link, tag);
+        }
+
+        // subscriptions only carry over if the event is listened to here
+        let subscriptions = Arc::make_mut(&mut self.subscriptions);
+        for event_id in carried.subscriptions {
+            if let Some(links) = subscriptions.get_mut(&event_id) {
+                links.insert(link);
+            }
         }
     }
 }
 
-type EventFn<E> = fn(&mut WorldQueue, &mut E);
-type EventMap<E> = IndexMap<TypeId, EventFn<E>>;
+/// Calls [`Pearl::on_transfer`] for every pearl of type `P` in `moved`.
+pub(super) fn transferred<P: Pearl>(world: &mut WorldQueue, remap: &LinkRemap, moved: &MovedLinks) {
+    for (old_link, new_link) in moved.iter() {
+        P::on_transfer(Transferred {
+            view: PearlView::new_unchecked(new_link.into_type(), world),
+            old_link: old_link.into_type(),
+            remap,
+        });
+    }
+}
+
+type EventFn<F> = for<'e> fn(&mut WorldQueue, &mut <F as EventFamily>::Event<'e>);
+type EventMap<F> = IndexMap<TypeId, EventFn<F>>;
+
+/// The type erased runners for a single [`EventFamily`].
+struct EventData {
+    name: &'static str,
+    runners: Box<dyn Any>,
+    listeners: fn(&dyn Any) -> Vec<TypeId>,
+}
+
+impl EventData {
+    pub fn new<F: EventFamily>() -> Self {
+        Self {
+            name: F::name(),
+            runners: Box::new(EventMap::<F>::new()),
+            listeners: |runners| {
+                let map = runners.downcast_ref::<EventMap<F>>().unwrap();
+                map.keys().copied().collect()
+            },
+        }
+    }
+
+    pub fn get<F: EventFamily>(&self) -> &EventMap<F> {
+        self.runners.downcast_ref().unwrap()
+    }
+
+    pub fn get_mut<F: EventFamily>(&mut self) -> &mut EventMap<F> {
+        self.runners.downcast_mut().unwrap()
+    }
+}
 
 /// A storage solution for multiple all types of [`Pearl`] structs.
 #[derive(Default)]
 pub struct World {
     map_data: HashMap<TypeId, MapData>,
-    maps: SparseHandleMap<Box<dyn Any>>,
-    events: HashMap<TypeId, Box<dyn Any>>,
+    maps: SparseHandleMap<AnyMap>,
+    events: HashMap<TypeId, EventData>,
+    pools: HashMap<TypeId, Box<dyn Any>>,
+    registrations: IndexSet<TypeId>,
 }
 
 impl World {
@@ -114,12 +361,15 @@ impl World {
         Self::default()
     }
 
+    /// Returns the number of pearl types that have at least one pearl in the world.
     pub fn types(&self) -> usize {
-        self.maps.len()
+        // maps may be kept alive while empty by pools and snapshots
+        self.maps.iter().filter(|(_, map)| !map.is_empty()).count()
     }
 
+    /// Returns `true` if the world contains no pearls.
     pub fn is_empty(&self) -> bool {
-        self.maps.is_empty()
+        self.maps.iter().all(|(_, map)| map.is_empty())
     }
 
     pub fn len<P: Pearl>(&self) -> usize {
@@ -129,14 +379,22 @@ impl World {
                 let Some(anymap) = self.maps.get(map_data.handle) else {
                     return 0;
                 };
-                let map = anymap.downcast_ref::<DenseHandleMap<P>>().unwrap();
+                let map = anymap.get::<P>();
                 map.len()
             }
         }
     }
 
     pub fn has<P: Pearl>(&self) -> bool {
-        self.map_data.contains_key(&TypeId::of::<P>())
+        match self.map_data.get(&TypeId::of::<P>()) {
+            None => false,
+            Some(map_data) => {
+                // the map may be kept alive while empty by a pool
+                let anymap = self.maps.get(map_data.handle).unwrap();
+                let map = anymap.get::<P>();
+                !map.is_empty()
+            }
+        }
     }
 
     pub fn contains<P: Pearl>(&self, link: Link<P>) -> bool {
@@ -144,31 +402,120 @@ impl World {
             return false;
         };
 
-        let map = anymap.downcast_ref::<DenseHandleMap<P>>().unwrap();
+        let map = anymap.get::<P>();
         map.contains(link.pearl_handle)
     }
 
     pub fn get<P: Pearl>(&self, link: Link<P>) -> Option<&P> {
         let anymap = self.maps.get(link.map_handle)?;
-        let map = anymap.downcast_ref::<DenseHandleMap<P>>().unwrap();
+        let map = anymap.get::<P>();
         map.get(link.pearl_handle)
     }
 
     pub fn get_mut<P: Pearl>(&mut self, link: Link<P>) -> Option<&mut P> {
         let anymap = self.maps.get_mut(link.map_handle)?;
-        let map = anymap.downcast_mut::<DenseHandleMap<P>>().unwrap();
+        let map = anymap.get_mut::<P>();
         map.get_mut(link.pearl_handle)
     }
 
+    /// Returns mutable references to all the pearls in `links` at the same time.
+    ///
+    /// # Errors
+    /// Returns [`AccessError::InvalidLink`] if any of the links are invalid,
+    /// and [`AccessError::Aliased`] if any two links point to the same pearl.
+    pub fn get_many_mut<P: Pearl, const N: usize>(
+        &mut self,
+        links: [Link<P>; N],
+    ) -> Result<[&mut P; N], AccessError> {
+        if !links.iter().all(|link| self.contains(*link)) {
+            return Err(AccessError::InvalidLink);
+        }
+
+        for (index, link) in links.iter().enumerate() {
+            if links[..index].contains(link) {
+                return Err(AccessError::Aliased);
+            }
+        }
+
+        // there may be no map to borrow from when there are no links
+        let Some(first) = links.first() else {
+            return Ok(links.map(|_| unreachable!()));
+        };
+
+        // all valid links of the same type point into the same map
+        let anymap = self.maps.get_mut(first.map_handle).unwrap();
+        let map = anymap.get_mut::<P>();
+        let pearls = map.get_disjoint_mut(links.map(|link| link.pearl_handle));
+        Ok(pearls.unwrap())
+    }
+
+    /// Returns mutable references to the pearls at `a` and `b` at the same time.
+    ///
+    /// # Errors
+    /// Returns [`AccessError::InvalidLink`] if either link is invalid,
+    /// and [`AccessError::Aliased`] if both links point to the same pearl.
+    pub fn get_pair_mut<A: Pearl, B: Pearl>(
+        &mut self,
+        a: Link<A>,
+        b: Link<B>,
+    ) -> Result<(&mut A, &mut B), AccessError> {
+        if !self.contains(a) || !self.contains(b) {
+            return Err(AccessError::InvalidLink);
+        }
+
+        // if both links share a map, then A and B are the same type
+        if a.map_handle == b.map_handle {
+            if a.pearl_handle.id() == b.pearl_handle.id() {
+                return Err(AccessError::Aliased);
+            }
+
+            let anymap = self.maps.get_mut(a.map_handle).unwrap();
+            let map = anymap.get_mut::<A>();
+            let [a, b] = map
+                .get_disjoint_mut([a.pearl_handle, b.pearl_handle.into_type()])
+                .unwrap();
+            let b = (b as &mut dyn Any).downcast_mut::<B>().unwrap();
+            return Ok((a, b));
+        }
+
+        let [a_anymap, b_anymap] = self
+            .maps
+            .get_disjoint_mut([a.map_handle, b.map_handle])
+            .unwrap();
+        let a_map = a_anymap.get_mut::<A>();
+        let b_map = b_anymap.get_mut::<B>();
+        Ok((
+            a_map.get_mut(a.pearl_handle).unwrap(),
+            b_map.get_mut(b.pearl_handle).unwrap(),
+        ))
+    }
+
     pub fn remove<P: Pearl>(&mut self, link: Link<P>) -> Option<P> {
         let anymap = self.maps.get_mut(link.map_handle)?;
-        let map = anymap.downcast_mut::<DenseHandleMap<P>>().unwrap();
+        let map = anymap.get_mut::<P>();
         let mut pearl = map.remove(link.pearl_handle)?;
 
-        // remove map and its data if map was emptied
-        if map.is_empty() {
-            self.map_data.remove(&TypeId::of::<P>()).unwrap();
-            self.maps.remove(link.map_handle).unwrap();
+        // remove map and its data if map was emptied and is not kept alive.
+        // maps are kept alive by pools, and by snapshots so that their map handles stay valid.
+        let map_data = self.map_data.get(&TypeId::of::<P>()).unwrap();
+        let keep_alive = map_data.snapshot.is_some() || self.pools.contains_key(&TypeId::of::<P>());
+        if map.is_empty() && !keep_alive {
+            self.drop_map(TypeId::of::<P>());
+        } else {
+            // otherwise remove the link from all its event subscriptions and labels
+            let map_data = self.map_data.get_mut(&TypeId::of::<P>()).unwrap();
+            let subscriptions = &mut map_data.subscriptions;
+            if subscriptions
+                .values()
+                .any(|links| links.contains(&link.into_type()))
+            {
+                for links in Arc::make_mut(subscriptions).values_mut() {
+                    links.swap_remove(&link.into_type());
+                }
+            }
+            if map_data.labels.has_labels(link.into_type()) {
+                Arc::make_mut(&mut map_data.labels).remove(link.into_type());
+            }
         }
 
         P::on_remove(Removed {
@@ -180,17 +527,220 @@ impl World {
         Some(pearl)
     }
 
+    /// Enables pooling for pearls of type `P`, keeping up to `capacity` recycled values.
+    ///
+    /// While the pool is enabled, the storage for `P` is kept alive even when it is empty.
+    pub fn enable_pool<P: Poolable>(&mut self, capacity: usize) {
+        use hashbrown::hash_map::Entry;
+        match self.pools.entry(TypeId::of::<P>()) {
+            Entry::Occupied(e) => {
+                let pool = e.into_mut().downcast_mut::<Pool<P>>().unwrap();
+                pool.values.truncate(capacity);
+                pool.capacity = capacity;
+            }
+            Entry::Vacant(e) => {
+                e.insert(Box::new(Pool::<P> {
+                    values: Vec::with_capacity(capacity),
+                    capacity,
+                }));
+            }
+        }
+    }
+
+    /// Disables pooling for pearls of type `P` and drops all pooled values.
+    pub fn disable_pool<P: Poolable>(&mut self) {
+        if self.pools.remove(&TypeId::of::<P>()).is_none() {
+            return;
+        }
+
+        // remove the map if it was only kept alive by the pool
+        let map_data = self.map_data.get(&TypeId::of::<P>());
+        if map_data.is_some_and(|map_data| map_data.snapshot.is_none()) && !self.has::<P>() {
+            self.drop_map(TypeId::of::<P>());
+        }
+    }
+
+    /// Returns the number of values waiting in the pool for `P`.
+    pub fn pooled<P: Poolable>(&self) -> usize {
+        match self.pools.get(&TypeId::of::<P>()) {
+            Some(pool) => pool.downcast_ref::<Pool<P>>().unwrap().values.len(),
+            None => 0,
+        }
+    }
+
+    /// Removes the pearl at `link` and returns its value to the pool for `P`.
+    ///
+    /// The value is [`reset`](Poolable::reset) before it is pooled.
+    /// If pooling is not enabled for `P`, or the pool is full, the value is dropped.
+    /// Returns `false` if the pearl does not exist.
+    ///
+    /// The pearl is removed using [`remove`](Self::remove),
+    /// so [`Pearl::on_remove`] runs before the value is reset and pooled.
+    pub fn recycle<P: Poolable>(&mut self, link: Link<P>) -> bool {
+        let Some(mut pearl) = self.remove(link) else {
+            return false;
+        };
+
+        if let Some(pool) = self.pools.get_mut(&TypeId::of::<P>()) {
+            let pool = pool.downcast_mut::<Pool<P>>().unwrap();
+            if pool.values.len() < pool.capacity {
+                pearl.reset();
+                pool.values.push(pearl);
+            }
+        }
+
+        true
+    }
+
+    /// Inserts a pearl taken from the pool for `P`, initialized using `init`.
+    ///
+    /// If the pool is empty, a new default value is used instead.
+    pub fn spawn_pooled<P: Poolable>(&mut self, init: impl FnOnce(&mut P)) -> Link<P> {
+        let pool = self.pools.get_mut(&TypeId::of::<P>());
+        let pool = pool.map(|pool| pool.downcast_mut::<Pool<P>>().unwrap());
+        let mut pearl = pool.and_then(|pool| pool.values.pop()).unwrap_or_default();
+        init(&mut pearl);
+        self.insert(pearl)
+    }
+
+    /// Removes every pearl of type `P`, along with all of its event registrations.
+    ///
+    /// When the storage for `P` empties on its own, its listeners keep their position in the dispatch order,
+    /// and return to it when `P` is inserted again.
+    /// Unregistering also forgets that position, so `P` will listen after every other type if it is inserted again.
+    /// Any pool or snapshot data for `P` is dropped.
+    ///
+    /// Returns the number of pearls that were removed.
+    pub fn unregister<P: Pearl>(&mut self) -> usize {
+        // drop the pool first so that it does not keep the storage alive
+        self.pools.remove(&TypeId::of::<P>());
+
+        let links = self.links_copied::<P>();
+        let count = links.len();
+        for link in links {
+            self.remove(link);
+        }
+
+        self.drop_map(TypeId::of::<P>());
+        self.registrations.shift_remove(&TypeId::of::<P>());
+        count
+    }
+
+    /// Drops the storage for a pearl type, and removes all of its event runners.
+    fn drop_map(&mut self, type_id: TypeId) {
+        let Some(map_data) = self.map_data.remove(&type_id) else {
+            return;
+        };
+
+        self.maps.remove(map_data.handle).unwrap();
+        for remover in map_data.events.values() {
+            remover(self);
+        }
+    }
+
     pub fn insert<P: Pearl>(&mut self, pearl: P) -> Link<P> {
         self.insert_then(pearl, |_| {})
     }
 
     pub fn insert_then<P: Pearl>(&mut self, pearl: P, then: impl FnOnce(PearlView<P>)) -> Link<P> {
+        let link = self.insert_quiet(pearl);
+        let mut queue = WorldQueue::new(self);
+        P::on_insert(Inserted {
+            view: PearlView::new_unchecked(link, &mut queue),
+        });
+        then(PearlView::new_unchecked(link, &mut queue));
+        link
+    }
+
+    /// Moves the pearl at `link` into `other`, and returns its new link.
+    ///
+    /// The pearl leaves this world like it was removed, so [`Pearl::on_remove`] is called here.
+    /// It is not inserted as a new pearl in `other`, so [`Pearl::on_insert`] is not called.
+    /// Instead, [`Pearl::on_transfer`] is called in `other` once the pearl has moved.
+    /// Its names, tags and subscriptions are carried over with it.
+    ///
+    /// Returns `None` if the pearl does not exist.
+    pub fn transfer<P: Pearl>(&mut self, link: Link<P>, other: &mut World) -> Option<Link<P>> {
+        let map_data = self.map_data.get(&TypeId::of::<P>())?;
+        let carried = map_data.carried_labels(link.into_type());
+        let pearl = self.remove(link)?;
+
+        let new_link = other.insert_quiet(pearl);
+        let map_data = other.map_data.get_mut(&TypeId::of::<P>()).unwrap();
+        map_data.apply_labels(new_link.into_type(), carried);
+
+        let mut remap = LinkRemap::default();
+        remap.insert(link, new_link);
+        let mut queue = WorldQueue::new(other);
+        P::on_transfer(Transferred {
+            view: PearlView::new_unchecked(new_link, &mut queue),
+            old_link: link,
+            remap: &remap,
+        });
+
+        Some(new_link)
+    }
+
+    /// Moves every pearl in `other` into this world,
+    /// and returns a [`LinkRemap`] from their old links to their new links.
+    ///
+    /// The whole world moves at once, so no pearls are removed or inserted,
+    /// and neither [`Pearl::on_remove`] nor [`Pearl::on_insert`] are called.
+    /// [`Pearl::on_transfer`] is called for every moved pearl after all of them have moved,
+    /// so that links between them can be remapped.
+    /// Names, tags and subscriptions are carried over,
+    /// and moved names replace any existing pearls with the same name.
+    pub fn merge(&mut self, mut other: World) -> LinkRemap {
+        let mut remap = LinkRemap::default();
+        let mut moved = Vec::new();
+        for (type_id, map_data) in other.map_data.drain() {
+            let anymap = other.maps.remove(map_data.handle).unwrap();
+            let links = (map_data.merge.insert)(self, anymap, map_data.handle, &mut remap);
+            if let Some(target) = self.map_data.get_mut(&type_id) {
+                for (old_link, new_link) in links.iter() {
+                    let carried = map_data.carried_labels(*old_link);
+                    target.apply_labels(*new_link, carried);
+                }
+            }
+
+            moved.push((map_data.merge.transferred, links));
+        }
+
+        let mut queue = WorldQueue::new(self);
+        for (transferred, links) in moved.iter() {
+            transferred(&mut queue, &remap, links);
+        }
+        drop(queue);
+
+        remap
+    }
+
+    /// Moves every pearl in `other` into this world,
+    /// and returns a [`LinkRemap`] from their old links to their new links.
+    ///
+    /// Pearls are moved like [`merge`](Self::merge),
+    /// so [`Pearl::on_transfer`] is called for every moved pearl after all of them have moved.
+    pub fn merge_send(&mut self, other: SendWorld) -> LinkRemap {
+        let mut remap = LinkRemap::default();
+        let moved = other.move_into(self, &mut remap);
+
+        let mut queue = WorldQueue::new(self);
+        for (transferred, links) in moved.iter() {
+            transferred(&mut queue, &remap, links);
+        }
+        drop(queue);
+
+        remap
+    }
+
+    /// Inserts a pearl without calling any [`Pearl`] hooks.
+    pub(super) fn insert_quiet<P: Pearl>(&mut self, pearl: P) -> Link<P> {
         use hashbrown::hash_map::Entry as E;
-        let link = match self.map_data.entry(TypeId::of::<P>()) {
+        match self.map_data.entry(TypeId::of::<P>()) {
             E::Occupied(e) => {
                 let map_data = e.into_mut();
                 let anymap = self.maps.get_mut(map_data.handle).unwrap();
-                let map = anymap.downcast_mut::<DenseHandleMap<P>>().unwrap();
+                let map = anymap.get_mut::<P>();
                 let pearl_handle = map.insert(pearl);
                 Link {
                     map_handle: map_data.handle,
@@ -200,30 +750,97 @@ impl World {
             E::Vacant(e) => {
                 let mut map = DenseHandleMap::new();
                 let pearl_handle = map.insert(pearl);
-                let map_handle = self.maps.insert(Box::new(map));
-                e.insert(MapData::new(map_handle)); // register events
+                let map_handle = self.maps.insert(AnyMap::new(map));
+                e.insert(MapData::new::<P>(map_handle)); // register events
+                self.registrations.insert(TypeId::of::<P>());
                 P::register(self);
                 Link {
                     map_handle,
                     pearl_handle,
                 }
             }
-        };
+        }
+    }
 
-        let mut queue = WorldQueue::new(self);
-        P::on_insert(Inserted {
-            view: PearlView::new_unchecked(link, &mut queue),
-        });
-        then(PearlView::new_unchecked(link, &mut queue));
-        link
+    /// Returns a snapshot of every pearl type that opted into snapshots.
+    ///
+    /// Types are opted in using [`EventSource::enable_snapshots`](crate::pearl::EventSource::enable_snapshots).
+    /// Snapshots are copy on write, so a type that has not been accessed mutably
+    /// since the last snapshot shares its storage with that snapshot, as long as it is still alive.
+    /// Names, tags and subscriptions are shared in the same way, until they are changed.
+    pub fn snapshot(&mut self) -> WorldSnapshot {
+        let mut snapshot = WorldSnapshot::default();
+        for (type_id, map_data) in self.map_data.iter_mut() {
+            let Some(data) = &mut map_data.snapshot else {
+                continue;
+            };
+
+            let anymap = self.maps.get_mut(map_data.handle).unwrap();
+            let last = data.last.as_ref().and_then(Weak::upgrade);
+            let map = match last {
+                Some(last) if !anymap.changed => last,
+                _ => {
+                    anymap.changed = false;
+                    let map = (data.clone)(anymap);
+                    data.last = Some(Arc::downgrade(&map));
+                    map
+                }
+            };
+
+            let entry = SnapshotEntry {
+                map,
+                subscriptions: map_data.subscriptions.clone(),
+                labels: map_data.labels.clone(),
+            };
+            snapshot.entries.insert(*type_id, entry);
+        }
+
+        snapshot
     }
 
-    pub fn links<P: Pearl>(&self) -> Links<P> {
+    /// Restores every pearl type that opted into snapshots to its state in `snapshot`.
+    ///
+    /// All links that were valid when the snapshot was taken will be valid again.
+    /// Links created after the snapshot was taken stay invalid, even once their slots are reused.
+    /// Types that did not exist when the snapshot was taken will be emptied.
+    /// No [`Pearl`] hooks are called while restoring.
+    pub fn restore(&mut self, snapshot: &WorldSnapshot) {
+        for (type_id, map_data) in self.map_data.iter_mut() {
+            let Some(data) = &mut map_data.snapshot else {
+                continue;
+            };
+
+            let anymap = self.maps.get_mut(map_data.handle).unwrap();
+            match snapshot.entries.get(type_id) {
+                Some(entry) => {
+                    (data.restore)(anymap, &entry.map);
+                    anymap.changed = false;
+                    data.last = Some(Arc::downgrade(&entry.map));
+                    map_data.subscriptions = entry.subscriptions.clone();
+                    map_data.labels = entry.labels.clone();
+                }
+                None => {
+                    (data.empty)(anymap);
+                    data.last = None;
+                    let subscriptions = Arc::make_mut(&mut map_data.subscriptions);
+                    subscriptions.values_mut().for_each(|s| s.clear());
+                    map_data.labels = Arc::default();
+                }
+            }
+        }
+    }
+
+    /// Spawns every pearl in `prefab`, and returns their links.
+    pub fn spawn_prefab(&mut self, prefab: &Prefab) -> PrefabLinks {
+        prefab.spawn(self)
+    }
+
+    pub fn links<P: Pearl>(&self) -> Links<'_, P> {
         match self.map_data.get(&TypeId::of::<P>()) {
             None => Links::empty(),
             Some(map_data) => {
                 let anymap = self.maps.get(map_data.handle).unwrap();
-                let map = anymap.downcast_ref::<DenseHandleMap<P>>().unwrap();
+                let map = anymap.get::<P>();
                 Links {
                     inner: map.handles(),
                     map_handle: map_data.handle,
@@ -237,7 +854,7 @@ impl World {
             None => LinksCopied::empty(),
             Some(map_data) => {
                 let anymap = self.maps.get(map_data.handle).unwrap();
-                let map = anymap.downcast_ref::<DenseHandleMap<P>>().unwrap();
+                let map = anymap.get::<P>();
                 LinksCopied {
                     inner: map.handles_copied().into_iter(),
                     map_handle: map_data.handle,
@@ -246,12 +863,12 @@ impl World {
         }
     }
 
-    pub fn pearls<P: Pearl>(&self) -> Pearls<P> {
+    pub fn pearls<P: Pearl>(&self) -> Pearls<'_, P> {
         match self.map_data.get(&TypeId::of::<P>()) {
             None => Pearls::empty(),
             Some(map_data) => {
                 let anymap = self.maps.get(map_data.handle).unwrap();
-                let map = anymap.downcast_ref::<DenseHandleMap<P>>().unwrap();
+                let map = anymap.get::<P>();
                 Pearls {
                     inner: map.values(),
                 }
@@ -259,12 +876,12 @@ impl World {
         }
     }
 
-    pub fn pearls_mut<P: Pearl>(&mut self) -> PearlsMut<P> {
+    pub fn pearls_mut<P: Pearl>(&mut self) -> PearlsMut<'_, P> {
         match self.map_data.get(&TypeId::of::<P>()) {
             None => PearlsMut::empty(),
             Some(map_data) => {
                 let anymap = self.maps.get_mut(map_data.handle).unwrap();
-                let map = anymap.downcast_mut::<DenseHandleMap<P>>().unwrap();
+                let map = anymap.get_mut::<P>();
                 PearlsMut {
                     inner: map.values_mut(),
                 }
@@ -272,12 +889,52 @@ impl World {
         }
     }
 
-    pub fn iter<P: Pearl>(&self) -> Iter<P> {
+    pub fn slice<P: Pearl>(&self) -> &[P] {
+        match self.map_data.get(&TypeId::of::<P>()) {
+            None => &[],
+            Some(map_data) => {
+                let anymap = self.maps.get(map_data.handle).unwrap();
+                let map = anymap.get::<P>();
+                map.as_slice()
+            }
+        }
+    }
+
+    pub fn slice_mut<P: Pearl>(&mut self) -> &mut [P] {
+        match self.map_data.get(&TypeId::of::<P>()) {
+            None => &mut [],
+            Some(map_data) => {
+                let anymap = self.maps.get_mut(map_data.handle).unwrap();
+                let map = anymap.get_mut::<P>();
+                map.as_mut_slice()
+            }
+        }
+    }
+
+    /// Returns the links of every pearl of type `P` along with the pearls as a mutable slice.
+    ///
+    /// The order of the links matches the order of the slice.
+    pub fn split_slice_mut<P: Pearl>(&mut self) -> (Links<'_, P>, &mut [P]) {
+        match self.map_data.get(&TypeId::of::<P>()) {
+            None => (Links::empty(), &mut []),
+            Some(map_data) => {
+                let anymap = self.maps.get_mut(map_data.handle).unwrap();
+                let (handles, pearls) = anymap.get_mut::<P>().split_mut();
+                let links = Links {
+                    inner: handles,
+                    map_handle: map_data.handle,
+                };
+                (links, pearls)
+            }
+        }
+    }
+
+    pub fn iter<P: Pearl>(&self) -> Iter<'_, P> {
         match self.map_data.get(&TypeId::of::<P>()) {
             None => Iter::empty(),
             Some(map_data) => {
                 let anymap = self.maps.get(map_data.handle).unwrap();
-                let map = anymap.downcast_ref::<DenseHandleMap<P>>().unwrap();
+                let map = anymap.get::<P>();
                 Iter {
                     inner: map.iter(),
                     map_handle: map_data.handle,
@@ -286,12 +943,12 @@ impl World {
         }
     }
 
-    pub fn iter_mut<P: Pearl>(&mut self) -> IterMut<P> {
+    pub fn iter_mut<P: Pearl>(&mut self) -> IterMut<'_, P> {
         match self.map_data.get(&TypeId::of::<P>()) {
             None => IterMut::empty(),
             Some(map_data) => {
                 let anymap = self.maps.get_mut(map_data.handle).unwrap();
-                let map = anymap.downcast_mut::<DenseHandleMap<P>>().unwrap();
+                let map = anymap.get_mut::<P>();
                 IterMut {
                     inner: map.iter_mut(),
                     map_handle: map_data.handle,
@@ -300,19 +957,248 @@ impl World {
         }
     }
 
+    /// Returns the name given to the pearl at `link`.
+    pub fn name<P: Pearl>(&self, link: Link<P>) -> Option<&str> {
+        self.labels::<P>()?.name(link.into_type())
+    }
+
+    /// Returns the link to the pearl of type `P` with `name`.
+    pub fn find<P: Pearl>(&self, name: &str) -> Option<Link<P>> {
+        Some(self.labels::<P>()?.find(name)?.into_type())
+    }
+
+    /// Sets the name of the pearl at `link`.
+    ///
+    /// Names are unique for each pearl type,
+    /// so any other pearl of type `P` with the same name will lose it.
+    /// Returns `false` if the pearl does not exist.
+    pub fn set_name<P: Pearl>(&mut self, link: Link<P>, name: impl Into<String>) -> bool {
+        match self.labels_mut(link) {
+            Some(labels) => {
+                labels.set_name(link.into_type(), name.into());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes and returns the name of the pearl at `link`.
+    pub fn clear_name<P: Pearl>(&mut self, link: Link<P>) -> Option<String> {
+        self.labels_mut(link)?.clear_name(link.into_type())
+    }
+
+    pub fn has_tag<P: Pearl>(&self, link: Link<P>, tag: &str) -> bool {
+        match self.labels::<P>() {
+            Some(labels) => labels.has_tag(link.into_type(), tag),
+            None => false,
+        }
+    }
+
+    /// Adds `tag` to the pearl at `link`.
+    ///
+    /// Returns `false` if the pearl does not exist or already has the tag.
+    pub fn add_tag<P: Pearl>(&mut self, link: Link<P>, tag: impl Into<String>) -> bool {
+        match self.labels_mut(link) {
+            Some(labels) => labels.add_tag(link.into_type(), tag.into()),
+            None => false,
+        }
+    }
+
+    /// Removes `tag` from the pearl at `link`.
+    ///
+    /// Returns `false` if the pearl did not have the tag.
+    pub fn remove_tag<P: Pearl>(&mut self, link: Link<P>, tag: &str) -> bool {
+        match self.labels_mut(link) {
+            Some(labels) => labels.remove_tag(link.into_type(), tag),
+            None => false,
+        }
+    }
+
+    /// Returns a copy of the links to all pearls of type `P` with `tag`.
+    pub fn links_with_tag<P: Pearl>(&self, tag: &str) -> LinksCopied<P> {
+        match self.map_data.get(&TypeId::of::<P>()) {
+            None => LinksCopied::empty(),
+            Some(map_data) => {
+                let links = map_data.labels.tagged(tag);
+                LinksCopied {
+                    inner: links
+                        .map(|link| link.pearl_handle.into_type())
+                        .collect::<Vec<_>>()
+                        .into_iter(),
+                    map_handle: map_data.handle,
+                }
+            }
+        }
+    }
+
+    fn labels<P: Pearl>(&self) -> Option<&Labels> {
+        Some(&self.map_data.get(&TypeId::of::<P>())?.labels)
+    }
+
+    fn labels_mut<P: Pearl>(&mut self, link: Link<P>) -> Option<&mut Labels> {
+        if !self.contains(link) {
+            return None;
+        }
+
+        let map_data = self.map_data.get_mut(&TypeId::of::<P>())?;
+        Some(Arc::make_mut(&mut map_data.labels))
+    }
+
+    /// Subscribes the pearl at `link` to events of type `E`.
+    ///
+    /// Returns `false` if the pearl does not exist,
+    /// or if `P` was not registered using [`listen_subscribed`](crate::pearl::EventSource::listen_subscribed).
+    pub fn subscribe<E: Event, P: Listener<E>>(&mut self, link: Link<P>) -> bool {
+        if !self.contains(link) {
+            return false;
+        }
+
+        match self.subscriptions_mut::<E, P>() {
+            Some(links) => {
+                links.insert(link.into_type());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Unsubscribes the pearl at `link` from events of type `E`.
+    ///
+    /// Returns `false` if the pearl was not subscribed.
+    pub fn unsubscribe<E: Event, P: Listener<E>>(&mut self, link: Link<P>) -> bool {
+        match self.subscriptions_mut::<E, P>() {
+            Some(links) => links.swap_remove(&link.into_type()),
+            None => false,
+        }
+    }
+
+    pub fn is_subscribed<E: Event, P: Listener<E>>(&self, link: Link<P>) -> bool {
+        match self.map_data.get(&TypeId::of::<P>()) {
+            None => false,
+            Some(map_data) => match map_data.subscriptions.get(&TypeId::of::<E>()) {
+                Some(links) => links.contains(&link.into_type()),
+                None => false,
+            },
+        }
+    }
+
+    fn subscriptions<E: Event, P: Pearl>(&self) -> Option<&IndexSet<Link<()>>> {
+        let map_data = self.map_data.get(&TypeId::of::<P>())?;
+        map_data.subscriptions.get(&TypeId::of::<E>())
+    }
+
+    fn subscriptions_mut<E: Event, P: Pearl>(&mut self) -> Option<&mut IndexSet<Link<()>>> {
+        let map_data = self.map_data.get_mut(&TypeId::of::<P>())?;
+        let subscriptions = Arc::make_mut(&mut map_data.subscriptions);
+        subscriptions.get_mut(&TypeId::of::<E>())
+    }
+
     pub fn trigger<E: Event>(&mut self, data: &mut E) {
+        self.trigger_family::<Owned<E>>(data);
+    }
+
+    /// Triggers an event from the [`EventFamily`] `F`.
+    ///
+    /// Unlike [`trigger`](Self::trigger), the event may borrow data.
+    pub fn trigger_family<F: EventFamily>(&mut self, data: &mut F::Event<'_>) {
         let mut queue = WorldQueue::new(self);
-        Self::trigger_nested::<E>(&mut queue, data);
+        Self::trigger_nested::<F>(&mut queue, data);
+    }
+
+    /// Asks every [`Responder`](crate::query::Responder) the query `Q` and collects their responses.
+    pub fn query_event<Q: Query>(&mut self, query: &Q) -> Vec<Q::Response> {
+        WorldQueue::new(self).query_event(query)
+    }
+
+    /// Folds all responses to the query `Q` into a single value.
+    ///
+    /// Returning [`ControlFlow::Break`] from `f` stops the query early.
+    pub fn query_fold<Q: Query, A>(
+        &mut self,
+        query: &Q,
+        init: A,
+        f: impl FnMut(A, Q::Response) -> ControlFlow<A, A>,
+    ) -> A {
+        WorldQueue::new(self).query_fold(query, init, f)
+    }
+
+    /// Returns the first response to the query `Q`, and stops the query.
+    pub fn query_first<Q: Query>(&mut self, query: &Q) -> Option<Q::Response> {
+        WorldQueue::new(self).query_first(query)
     }
 
-    pub(crate) fn trigger_nested<E: Event>(queue: &mut WorldQueue, data: &mut E) {
-        let Some(anymap) = queue.world.events.get(&TypeId::of::<E>()) else {
+    /// Returns `true` if any response to the query `Q` is `true`, stopping at the first one.
+    pub fn query_any<Q: Query<Response = bool>>(&mut self, query: &Q) -> bool {
+        WorldQueue::new(self).query_any(query)
+    }
+
+    /// Returns the sum of all responses to the query `Q`.
+    pub fn query_sum<Q: Query>(&mut self, query: &Q) -> Q::Response
+    where
+        Q::Response: Sum,
+    {
+        WorldQueue::new(self).query_sum(query)
+    }
+
+    /// Returns a report of every pearl type and event type in this world.
+    ///
+    /// Operations can only be pending inside a [`WorldQueue`],
+    /// so use [`WorldQueue::stats`] to include them.
+    pub fn stats(&self) -> WorldStats {
+        let mut pearls = Vec::with_capacity(self.map_data.len());
+        for map_data in self.map_data.values() {
+            let anymap = self.maps.get(map_data.handle).unwrap();
+            let usage = (map_data.usage)(anymap);
+            let events = map_data.events.keys();
+            pearls.push(PearlStats {
+                name: map_data.name,
+                count: usage.count,
+                capacity: usage.capacity,
+                bytes: usage.bytes,
+                events: events
+                    .filter_map(|id| Some(self.events.get(id)?.name))
+                    .collect(),
+            });
+        }
+
+        let mut events = Vec::with_capacity(self.events.len());
+        for event_data in self.events.values() {
+            let listeners = (event_data.listeners)(event_data.runners.as_ref());
+            let listeners = listeners.iter();
+            events.push(EventStats {
+                name: event_data.name,
+                listeners: listeners
+                    .filter_map(|id| Some(self.map_data.get(id)?.name))
+                    .collect(),
+            });
+        }
+
+        pearls.sort_by_key(|stats| stats.name);
+        events.sort_by_key(|stats| stats.name);
+        WorldStats {
+            pearls,
+            events,
+            pending: PendingStats::default(),
+        }
+    }
+
+    pub(crate) fn trigger_nested<F: EventFamily>(queue: &mut WorldQueue, data: &mut F::Event<'_>) {
+        let Some(event_data) = queue.world.events.get(&TypeId::of::<F>()) else {
             return;
         };
 
-        let map = anymap.downcast_ref::<EventMap<E>>().unwrap();
+        let map = event_data.get::<F>();
         let runners = map.values().cloned().collect::<Vec<_>>();
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("trigger", event = F::name(), listeners = runners.len(),)
+            .entered();
+
         for runner in runners {
+            if F::is_done(data) {
+                return;
+            }
+
             runner(queue, data);
         }
     }
@@ -320,41 +1206,157 @@ impl World {
 
 // seal event source impl so it cannot be called externally
 mod sealed {
+    use crate::{
+        pearl::{BatchListener, EventSource},
+        query::{Query, QueryFamily, Responder},
+    };
+
     use super::*;
 
-    impl<P> crate::pearl::EventSource<P> for World {
+    impl<P: Pearl> EventSource<P> for World {
         fn listen<E: Event>(&mut self)
         where
-            P: crate::pearl::Listener<E>,
+            P: Listener<E>,
+        {
+            EventSource::<P>::listen_family::<Owned<E>>(self);
+        }
+
+        fn listen_family<F: EventFamily>(&mut self)
+        where
+            P: FamilyListener<F>,
+        {
+            self.insert_runner::<P, F>(|world, data| {
+                #[cfg(feature = "tracing")]
+                let span = crate::trace::ListenerSpan::<P, F>::enter();
+
+                let links = world.links_copied::<P>();
+                #[cfg(feature = "tracing")]
+                let count = links.len();
+                for link in links {
+                    if F::is_done(data) {
+                        break;
+                    }
+
+                    let view = PearlView::new_unchecked(link, world);
+                    <P as FamilyListener<F>>::trigger(view, data);
+                }
+
+                #[cfg(feature = "tracing")]
+                span.exit(count);
+            });
+        }
+
+        fn listen_subscribed<E: Event>(&mut self)
+        where
+            P: Listener<E>,
+        {
+            // create the subscription set for E
+            let map_data = self.map_data.get_mut(&TypeId::of::<P>()).unwrap();
+            Arc::make_mut(&mut map_data.subscriptions).insert(TypeId::of::<E>(), IndexSet::new());
+
+            self.insert_runner::<P, Owned<E>>(|world, data| {
+                let Some(links) = world.world.subscriptions::<E, P>() else {
+                    return;
+                };
+
+                #[cfg(feature = "tracing")]
+                let span = crate::trace::ListenerSpan::<P, Owned<E>>::enter();
+
+                let links = links.iter().map(|link| link.into_type());
+                let links = links.collect::<Vec<Link<P>>>();
+                #[cfg(feature = "tracing")]
+                let count = links.len();
+                for link in links {
+                    // skip pearls that were unsubscribed by an earlier listener
+                    if !world.world.is_subscribed::<E, P>(link) {
+                        continue;
+                    }
+
+                    let view = PearlView::new_unchecked(link, world);
+                    <P as Listener<E>>::trigger(view, data);
+                }
+
+                #[cfg(feature = "tracing")]
+                span.exit(count);
+            });
+        }
+
+        fn respond<Q: Query>(&mut self)
+        where
+            P: Responder<Q>,
         {
+            EventSource::<P>::listen_family::<QueryFamily<Q>>(self);
+        }
+
+        fn enable_snapshots(&mut self)
+        where
+            P: Clone + Send + Sync,
+        {
+            let map_data = self.map_data.get_mut(&TypeId::of::<P>()).unwrap();
+            map_data.snapshot = Some(SnapshotData {
+                clone: |anymap| Arc::new(anymap.get::<P>().clone()),
+                restore: |anymap, map| {
+                    let map = map.downcast_ref::<DenseHandleMap<P>>().unwrap();
+                    anymap.get_mut::<P>().restore_from(map);
+                },
+                empty: |anymap| anymap.get_mut::<P>().restore_from(&DenseHandleMap::new()),
+                last: None,
+            });
+        }
+
+        fn listen_batch<E: Event>(&mut self)
+        where
+            P: BatchListener<E>,
+        {
+            self.insert_runner::<P, Owned<E>>(|world, data| {
+                #[cfg(feature = "tracing")]
+                let span = crate::trace::ListenerSpan::<P, Owned<E>>::enter();
+                #[cfg(feature = "tracing")]
+                let count = world.world.len::<P>();
+
+                P::trigger_all(BatchView::new(world), data);
+
+                #[cfg(feature = "tracing")]
+                span.exit(count);
+            });
+        }
+    }
+
+    impl World {
+        fn insert_runner<P: Pearl, F: EventFamily>(&mut self, runner: EventFn<F>) {
             // create pearl and event ids
             let pearl_id = TypeId::of::<P>();
-            let event_id = TypeId::of::<E>();
+            let event_id = TypeId::of::<F>();
 
-            // get the event map associated with E
+            // get the event map associated with F
             use hashbrown::hash_map::Entry;
             let map = match self.events.entry(event_id) {
-                Entry::Occupied(e) => e.into_mut().downcast_mut::<EventMap<E>>().unwrap(),
-                Entry::Vacant(e) => e
-                    .insert(Box::new(EventMap::<E>::new()))
-                    .downcast_mut::<EventMap<E>>()
-                    .unwrap(),
+                Entry::Occupied(e) => e.into_mut().get_mut::<F>(),
+                Entry::Vacant(e) => e.insert(EventData::new::<F>()).get_mut::<F>(),
             };
 
-            // insert the event trigger code for P
-            map.insert(pearl_id, |world, data| {
-                for link in world.links_copied::<P>() {
-                    let view = PearlView::new_unchecked(link, world);
-                    P::trigger(view, data);
-                }
-            });
+            // insert the event trigger code for P, ordered by when each type was first registered.
+            // a runner that is already registered is replaced in place.
+            let registrations = &self.registrations;
+            let order = registrations.get_index_of(&pearl_id);
+            let index = map
+                .keys()
+                .position(|id| registrations.get_index_of(id) > order)
+                .unwrap_or(map.len());
+            match map.contains_key(&pearl_id) {
+                true => map.insert(pearl_id, runner),
+                false => map.shift_insert(index, pearl_id, runner),
+            };
 
             // add the event id and remover to the pearls map data
             let map_data = self.map_data.get_mut(&pearl_id).unwrap();
             map_data.events.insert(event_id, |world| {
-                let anymap = world.events.get_mut(&TypeId::of::<E>()).unwrap();
-                let map = anymap.downcast_mut::<EventMap<E>>().unwrap();
-                map.swap_remove(&TypeId::of::<P>());
+                let event_data = world.events.get_mut(&TypeId::of::<F>()).unwrap();
+                let map = event_data.get_mut::<F>();
+                map.shift_remove(&TypeId::of::<P>());
+                if map.is_empty() {
+                    world.events.remove(&TypeId::of::<F>());
+                }
             });
         }
     }
@@ -362,7 +1364,7 @@ mod sealed {
 
 pub struct Links<'a, P> {
     inner: handle_map::map::dense::Handles<'a, P>,
-    map_handle: Handle<Box<dyn Any>>,
+    map_handle: Handle<AnyMap>,
 }
 
 impl<'a, P> Links<'a, P> {
@@ -388,7 +1390,7 @@ impl<'a, P> Iterator for Links<'a, P> {
 
 pub struct LinksCopied<P> {
     inner: std::vec::IntoIter<Handle<P>>,
-    map_handle: Handle<Box<dyn Any>>,
+    map_handle: Handle<AnyMap>,
 }
 
 impl<P> LinksCopied<P> {
@@ -410,8 +1412,14 @@ impl<P> Iterator for LinksCopied<P> {
             pearl_handle,
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
 }
 
+impl<P> ExactSizeIterator for LinksCopied<P> {}
+
 pub struct Pearls<'a, P> {
     inner: handle_map::map::dense::Values<'a, P>,
 }
@@ -454,7 +1462,7 @@ impl<'a, P> Iterator for PearlsMut<'a, P> {
 
 pub struct Iter<'a, P> {
     inner: handle_map::map::dense::Iter<'a, P>,
-    map_handle: Handle<Box<dyn Any>>,
+    map_handle: Handle<AnyMap>,
 }
 
 impl<'a, P> Iter<'a, P> {
@@ -483,7 +1491,7 @@ impl<'a, P> Iterator for Iter<'a, P> {
 
 pub struct IterMut<'a, P> {
     inner: handle_map::map::dense::IterMut<'a, P>,
-    map_handle: Handle<Box<dyn Any>>,
+    map_handle: Handle<AnyMap>,
 }
 
 impl<'a, P> IterMut<'a, P> {
@@ -554,10 +1562,402 @@ impl<'a, P: Pearl> Removed<'a, P> {
     }
 
     pub fn world(&self) -> &World {
-        &self.world
+        self.world
     }
 
     pub fn world_mut(&mut self) -> &mut World {
-        &mut self.world
+        self.world
+    }
+}
+
+pub struct Transferred<'a, 'world, P: Pearl> {
+    view: PearlView<'a, 'world, P>,
+    old_link: Link<P>,
+    remap: &'a LinkRemap,
+}
+
+impl<'a, 'world, P: Pearl> DerefMut for Transferred<'a, 'world, P> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.view
+    }
+}
+
+impl<'a, 'world, P: Pearl> Deref for Transferred<'a, 'world, P> {
+    type Target = PearlView<'a, 'world, P>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.view
+    }
+}
+
+impl<'a, 'world, P: Pearl> Transferred<'a, 'world, P> {
+    /// Returns the link the pearl had in the world it was moved from.
+    pub fn old_link(&self) -> Link<P> {
+        self.old_link
+    }
+
+    /// Returns the remap for every pearl that moved along with this one.
+    pub fn remap(&self) -> &'a LinkRemap {
+        self.remap
+    }
+}
+
+/// A map from the old links of moved pearls to their new links.
+///
+/// Created when moving pearls using [`World::transfer`] or [`World::merge`],
+/// or built manually to remap saved links to the pearls they were loaded as.
+#[derive(Debug, Default)]
+pub struct LinkRemap {
+    links: HashMap<Link<()>, Link<()>>,
+}
+
+impl LinkRemap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.links.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    /// Returns the new link for the pearl that was at `link`.
+    ///
+    /// Returns `None` if that pearl was not moved.
+    pub fn get<P: Pearl>(&self, link: Link<P>) -> Option<Link<P>> {
+        let new_link = self.links.get(&link.into_type())?;
+        Some(new_link.into_type())
+    }
+
+    /// Replaces `link` with its new link, returning `false` if its pearl was not moved.
+    pub fn remap<P: Pearl>(&self, link: &mut Link<P>) -> bool {
+        match self.get(*link) {
+            Some(new_link) => {
+                *link = new_link;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Maps `old_link` to `new_link`, replacing any previous mapping for `old_link`.
+    pub fn insert<P: Pearl>(&mut self, old_link: Link<P>, new_link: Link<P>) {
+        self.links
+            .insert(old_link.into_type(), new_link.into_type());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pearl::{EventSource, FamilyListener, Listener, Poolable};
+
+    use super::*;
+
+    struct Ping;
+
+    struct Counter(u32);
+
+    impl Pearl for Counter {
+        fn register(source: &mut impl EventSource<Self>) {
+            source.listen_subscribed::<Ping>();
+        }
+    }
+
+    impl Listener<Ping> for Counter {
+        fn trigger(mut pearl: PearlView<Self>, _: &mut Ping) {
+            pearl.0 += 1;
+        }
+    }
+
+    #[test]
+    fn subscriptions() {
+        let mut world = World::new();
+        let c1 = world.insert(Counter(0));
+        let c2 = world.insert(Counter(0));
+
+        assert!(world.subscribe::<Ping, _>(c1));
+        world.trigger(&mut Ping);
+        assert!(world.get(c1).unwrap().0 == 1);
+        assert!(world.get(c2).unwrap().0 == 0);
+
+        assert!(world.unsubscribe::<Ping, _>(c1));
+        assert!(!world.unsubscribe::<Ping, _>(c1));
+        world.trigger(&mut Ping);
+        assert!(world.get(c1).unwrap().0 == 1);
+
+        assert!(world.subscribe::<Ping, _>(c2));
+        world.remove(c2);
+        assert!(!world.is_subscribed::<Ping, _>(c2));
+    }
+
+    struct Log;
+    impl EventFamily for Log {
+        type Event<'a> = &'a mut Vec<u32>;
+    }
+
+    struct Logger(u32);
+
+    impl Pearl for Logger {
+        fn register(source: &mut impl EventSource<Self>) {
+            source.listen_family::<Log>();
+        }
+    }
+
+    impl FamilyListener<Log> for Logger {
+        fn trigger(pearl: PearlView<Self>, event: &mut &mut Vec<u32>) {
+            event.push(pearl.0);
+        }
+    }
+
+    #[test]
+    fn borrowed_events() {
+        let mut world = World::new();
+        world.insert(Logger(1));
+        world.insert(Logger(2));
+
+        let mut log = Vec::new();
+        world.trigger_family::<Log>(&mut &mut log);
+        assert!(log == [1, 2]);
+    }
+
+    #[test]
+    fn names_and_tags() {
+        let mut world = World::new();
+        let c1 = world.insert(Counter(1));
+        let c2 = world.insert(Counter(2));
+
+        assert!(world.set_name(c1, "player"));
+        assert!(world.find::<Counter>("player") == Some(c1));
+        assert!(world.set_name(c2, "player"));
+        assert!(world.find::<Counter>("player") == Some(c2));
+        assert!(world.name(c1).is_none());
+
+        assert!(world.add_tag(c1, "enemy"));
+        assert!(world.add_tag(c2, "enemy"));
+        assert!(!world.add_tag(c2, "enemy"));
+        assert!(world.links_with_tag::<Counter>("enemy").count() == 2);
+
+        world.remove(c2);
+        assert!(world.find::<Counter>("player").is_none());
+        assert!(world.links_with_tag::<Counter>("enemy").eq([c1]));
+    }
+
+    #[test]
+    fn disjoint_access() {
+        let mut world = World::new();
+        let c1 = world.insert(Counter(1));
+        let c2 = world.insert(Counter(2));
+        let l1 = world.insert(Logger(3));
+
+        let [p1, p2] = world.get_many_mut([c1, c2]).unwrap();
+        std::mem::swap(&mut p1.0, &mut p2.0);
+        assert!(world.get(c1).unwrap().0 == 2);
+        assert!(world.get_many_mut([c1, c1]).err() == Some(AccessError::Aliased));
+
+        let (counter, logger) = world.get_pair_mut(c1, l1).unwrap();
+        std::mem::swap(&mut counter.0, &mut logger.0);
+        assert!(world.get(l1).unwrap().0 == 2);
+        assert!(world.get_pair_mut(c2, c2).err() == Some(AccessError::Aliased));
+
+        world.remove(c2);
+        assert!(world.get_pair_mut(c1, c2).err() == Some(AccessError::InvalidLink));
+    }
+
+    #[derive(Default)]
+    struct Bullet {
+        speed: u32,
+    }
+
+    impl Pearl for Bullet {}
+    impl Poolable for Bullet {
+        fn reset(&mut self) {
+            self.speed = 0;
+        }
+    }
+
+    #[test]
+    fn pooling() {
+        let mut world = World::new();
+        world.enable_pool::<Bullet>(1);
+
+        let b1 = world.spawn_pooled::<Bullet>(|b| b.speed = 5);
+        let b2 = world.spawn_pooled::<Bullet>(|b| b.speed = 6);
+        assert!(world.recycle(b1));
+        assert!(world.recycle(b2));
+        assert!(!world.recycle(b2));
+        assert!(world.pooled::<Bullet>() == 1);
+
+        // the empty map is kept alive by the pool, but is not counted
+        assert!(!world.has::<Bullet>());
+        assert!(world.types() == 0);
+        assert!(world.is_empty());
+
+        let b3 = world.spawn_pooled::<Bullet>(|b| assert!(b.speed == 0));
+        assert!(b3 != b1 && b3 != b2);
+        assert!(world.pooled::<Bullet>() == 0);
+        assert!(world.types() == 1);
+
+        world.recycle(b3);
+        world.disable_pool::<Bullet>();
+        assert!(world.types() == 0);
+    }
+
+    #[derive(Clone)]
+    struct Health(u32);
+
+    impl Pearl for Health {
+        fn register(source: &mut impl EventSource<Self>) {
+            source.enable_snapshots();
+        }
+    }
+
+    #[test]
+    fn snapshot_restore() {
+        let mut world = World::new();
+        let h1 = world.insert(Health(10));
+        world.add_tag(h1, "alive");
+        let snapshot = world.snapshot();
+
+        // unchanged types share storage and labels with the previous snapshot
+        let unchanged = world.snapshot();
+        let entry = |s: &WorldSnapshot| s.entries[&TypeId::of::<Health>()].clone();
+        assert!(Arc::ptr_eq(&entry(&snapshot).map, &entry(&unchanged).map));
+        assert!(Arc::ptr_eq(
+            &entry(&snapshot).labels,
+            &entry(&unchanged).labels
+        ));
+
+        world.get_mut(h1).unwrap().0 = 5;
+        let h2 = world.insert(Health(20));
+        world.set_name(h1, "hero");
+        world.remove(h1);
+        let h3 = world.insert(Health(30));
+
+        world.restore(&snapshot);
+        assert!(world.get(h1).unwrap().0 == 10);
+        assert!(world.has_tag(h1, "alive"));
+        assert!(!world.contains(h2) && !world.contains(h3));
+        assert!(world.find::<Health>("hero").is_none());
+
+        // links created after the snapshot are not handed out again
+        world.remove(h1);
+        let new = [world.insert(Health(40)), world.insert(Health(50))];
+        assert!(!new.contains(&h1) && !new.contains(&h2) && !new.contains(&h3));
+        assert!(!world.contains(h2) && !world.contains(h3));
+
+        // the map is kept alive so that old links stay valid after emptying
+        for link in new {
+            world.remove(link);
+        }
+        world.restore(&snapshot);
+        assert!(world.get(h1).unwrap().0 == 10);
+
+        // the cached storage is dropped along with the last snapshot holding it
+        drop((snapshot, unchanged));
+        let map_data = &world.map_data[&TypeId::of::<Health>()];
+        let last = map_data.snapshot.as_ref().unwrap().last.as_ref().unwrap();
+        assert!(last.upgrade().is_none());
+    }
+
+    struct Node {
+        next: Option<Link<Node>>,
+    }
+
+    impl Pearl for Node {
+        fn on_transfer(mut pearl: Transferred<Self>) {
+            let remap = pearl.remap();
+            if let Some(next) = &mut pearl.next {
+                if !remap.remap(next) {
+                    pearl.next = None;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn transfer_and_merge() {
+        let mut source = World::new();
+        let tail = source.insert(Node { next: None });
+        let head = source.insert(Node { next: Some(tail) });
+        source.set_name(head, "head");
+
+        // transferring a single pearl drops links to pearls that did not move
+        let mut other = World::new();
+        let moved = source.transfer(head, &mut other).unwrap();
+        assert!(!source.contains(head));
+        assert!(other.get(moved).unwrap().next.is_none());
+        assert!(other.find::<Node>("head") == Some(moved));
+
+        // merging moves everything and remaps the links between moved pearls
+        let head = source.insert(Node { next: Some(tail) });
+        let remap = other.merge(source);
+        let new_head = remap.get(head).unwrap();
+        assert!(remap.len() == 2);
+        assert!(other.len::<Node>() == 3);
+        assert!(other.get(new_head).unwrap().next == remap.get(tail));
+    }
+
+    struct Order(Vec<&'static str>);
+
+    struct First;
+    struct Second;
+
+    impl Pearl for First {
+        fn register(source: &mut impl EventSource<Self>) {
+            source.listen::<Order>();
+        }
+    }
+
+    impl Pearl for Second {
+        fn register(source: &mut impl EventSource<Self>) {
+            source.listen::<Order>();
+        }
+    }
+
+    impl Listener<Order> for First {
+        fn trigger(_: PearlView<Self>, event: &mut Order) {
+            event.0.push("first");
+        }
+    }
+
+    impl Listener<Order> for Second {
+        fn trigger(_: PearlView<Self>, event: &mut Order) {
+            event.0.push("second");
+        }
+    }
+
+    fn dispatch_order(world: &mut World) -> Vec<&'static str> {
+        let mut order = Order(Vec::new());
+        world.trigger(&mut order);
+        order.0
+    }
+
+    #[test]
+    fn empty_and_refill() {
+        let mut world = World::new();
+        let first = world.insert(First);
+        world.insert(Second);
+        assert!(dispatch_order(&mut world) == ["first", "second"]);
+
+        // emptying a type removes its runners, and refilling keeps its position
+        world.remove(first);
+        assert!(world.stats().event::<Order>().unwrap().listeners.len() == 1);
+        assert!(dispatch_order(&mut world) == ["second"]);
+        world.insert(First);
+        assert!(dispatch_order(&mut world) == ["first", "second"]);
+
+        // unregistering forgets the position
+        assert!(world.unregister::<First>() == 1);
+        world.insert(First);
+        assert!(dispatch_order(&mut world) == ["second", "first"]);
+
+        // the event itself is removed once nothing listens to it
+        world.unregister::<First>();
+        world.unregister::<Second>();
+        assert!(world.stats().events.is_empty());
+        assert!(world.is_empty());
     }
 }
@@ -0,0 +1,124 @@
+use std::rc::Rc;
+
+use boba_core::{
+    world::{Link, PearlView},
+    Pearl,
+};
+
+use crate::{Connection, SignalBuilder, WorldSignalExt};
+
+/// Passes a message to a listener after filtering or transforming it.
+type Adapt<T, U> = Rc<dyn Fn(&mut T, &mut dyn FnMut(&mut U))>;
+
+/// Adds listeners to a [`SignalBuilder`] that only receive some messages,
+/// or receive them transformed into another type.
+///
+/// Created using [`filter`](crate::SignalRegister::filter) or [`map`](crate::SignalRegister::map),
+/// and chained to build up the adapter before adding a listener.
+/// Listeners that filter out a message do not produce a result for it,
+/// and changes made to a mapped message are not seen by any other listeners.
+pub struct SignalAdapter<'a, T: 'static, R: 'static, U: 'static> {
+    builder: &'a mut SignalBuilder<T, R>,
+    adapt: Adapt<T, U>,
+}
+
+impl<'a, T: 'static, R: 'static> SignalAdapter<'a, T, R, T> {
+    /// Creates an adapter that passes messages through unchanged.
+    pub(crate) fn new(builder: &'a mut SignalBuilder<T, R>) -> Self {
+        Self {
+            builder,
+            adapt: Rc::new(|data, listener| listener(data)),
+        }
+    }
+}
+
+impl<'a, T: 'static, R: 'static, U: 'static> SignalAdapter<'a, T, R, U> {
+    /// Only passes on messages that pass `filter`.
+    pub fn filter(self, filter: impl Fn(&U) -> bool + 'static) -> Self {
+        let adapt = self.adapt;
+        Self {
+            builder: self.builder,
+            adapt: Rc::new(move |data, listener| {
+                adapt(data, &mut |message| {
+                    if filter(message) {
+                        listener(message);
+                    }
+                })
+            }),
+        }
+    }
+
+    /// Transforms messages using `map` before passing them on.
+    pub fn map<V: 'static>(self, map: impl Fn(&U) -> V + 'static) -> SignalAdapter<'a, T, R, V> {
+        let adapt = self.adapt;
+        SignalAdapter {
+            builder: self.builder,
+            adapt: Rc::new(move |data, listener| {
+                adapt(data, &mut |message| listener(&mut map(message)));
+            }),
+        }
+    }
+
+    /// Adds `listener` for the pearl at `link`, and returns its [`Connection`].
+    pub fn add_listener<P: Pearl>(
+        self,
+        link: Link<P>,
+        listener: impl Fn(PearlView<P>, &mut U) -> R + 'static,
+    ) -> Connection {
+        self.connect(link, false, listener)
+    }
+
+    /// Adds `listener` for the pearl at `link` that is disconnected after it is first called.
+    ///
+    /// Messages that are filtered out do not count as a call.
+    pub fn add_once_listener<P: Pearl>(
+        self,
+        link: Link<P>,
+        listener: impl Fn(PearlView<P>, &mut U) -> R + 'static,
+    ) -> Connection {
+        self.connect(link, true, listener)
+    }
+
+    /// Relays messages to the signal of the pearl at `link`, transformed using `map`.
+    ///
+    /// See [`SignalRegister::forward_to`](crate::SignalRegister::forward_to).
+    pub fn forward_to<P: Pearl, V: 'static, R2: 'static>(
+        self,
+        link: Link<P>,
+        signal: impl Fn(&P) -> &SignalBuilder<V, R2> + 'static,
+        map: impl Fn(&U) -> V + 'static,
+    ) -> Connection {
+        let adapt = self.adapt;
+        self.builder
+            .add_link_listener(link, None, false, move |mut view, data| {
+                let mut forwarded = None;
+                adapt(data, &mut |message| forwarded = Some(map(message)));
+                if let Some(message) = forwarded {
+                    let forwarded = signal(&view).build(message);
+                    view.world_mut().send_signal(forwarded);
+                }
+
+                None
+            })
+    }
+
+    fn connect<P: Pearl>(
+        self,
+        link: Link<P>,
+        once: bool,
+        listener: impl Fn(PearlView<P>, &mut U) -> R + 'static,
+    ) -> Connection {
+        let adapt = self.adapt;
+        self.builder
+            .add_link_listener(link, None, once, move |view, data| {
+                let mut view = Some(view);
+                let mut result = None;
+                adapt(data, &mut |message| {
+                    if let Some(view) = view.take() {
+                        result = Some(listener(view, message));
+                    }
+                });
+                result
+            })
+    }
+}
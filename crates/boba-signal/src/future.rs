@@ -0,0 +1,99 @@
+use std::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+use crate::{
+    signal::{DeadSet, Reach, Sender},
+    Connection,
+};
+
+struct FutureState<T> {
+    message: Option<T>,
+    waker: Option<Waker>,
+    /// Set once the sender has fired, after which its listener is removed by its builder.
+    fired: bool,
+}
+
+/// A future that resolves to a clone of the next message sent by a [`SignalBuilder`](crate::SignalBuilder).
+///
+/// Created using [`SignalBuilder::next_future`](crate::SignalBuilder::next_future).
+/// It does not depend on any executor, and wakes its task when the signal is sent.
+/// Only signals built after the future was created will resolve it.
+pub struct SignalFuture<T> {
+    state: Rc<RefCell<FutureState<T>>>,
+    connection: Connection,
+    dead: DeadSet,
+}
+
+impl<T: Clone + 'static> SignalFuture<T> {
+    /// Creates a future, connecting the one-shot sender that resolves it using `connect`.
+    ///
+    /// The sender reports itself as unreachable after it fires,
+    /// and the future marks its connection as dead in `dead` if it is dropped before that,
+    /// so that it is removed from its builder.
+    pub(crate) fn new<R: 'static>(
+        dead: DeadSet,
+        connect: impl FnOnce(Sender<T, R>) -> Connection,
+    ) -> Self {
+        let state = Rc::new(RefCell::new(FutureState {
+            message: None,
+            waker: None,
+            fired: false,
+        }));
+
+        let weak = Rc::downgrade(&state);
+        let sender: Sender<T, R> = Rc::new(move |_, message, _| {
+            let Some(state) = weak.upgrade() else {
+                return Reach::Dead;
+            };
+
+            let mut state = state.borrow_mut();
+            if !state.fired {
+                state.fired = true;
+                state.message = Some(message.clone());
+                let waker = state.waker.take();
+                drop(state);
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+            }
+
+            Reach::Dead
+        });
+
+        let connection = connect(sender);
+        Self {
+            state,
+            connection,
+            dead,
+        }
+    }
+}
+
+impl<T> Drop for SignalFuture<T> {
+    fn drop(&mut self) {
+        // a sender that has fired is already reported as dead by the signal that sent it
+        if !self.state.borrow().fired {
+            self.dead.borrow_mut().insert(self.connection);
+        }
+    }
+}
+
+impl<T> Future for SignalFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+        match state.message.take() {
+            Some(message) => Poll::Ready(message),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -1,3 +1,11 @@
+mod adapter;
+mod future;
+mod queue;
+mod registry;
 mod signal;
 
+pub use adapter::*;
+pub use future::*;
+pub use queue::*;
+pub use registry::*;
 pub use signal::*;
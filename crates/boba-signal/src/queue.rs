@@ -0,0 +1,159 @@
+use std::marker::PhantomData;
+
+use boba_core::{
+    pearl::{Event, EventSource, Listener},
+    world::{PearlView, WorldQueue},
+    Pearl,
+};
+
+use crate::{Signal, WorldSignalExt};
+
+type QueuedSignal = Box<dyn FnOnce(&mut WorldQueue)>;
+
+/// A pearl that collects signals and sends them all when the event `E` is triggered.
+///
+/// This is used to send signals at a chosen phase of the frame,
+/// for example `SignalQueue<MilkTea<Update>>` sends its signals once per update.
+/// Signals queued while the queue is being sent will wait for the next time `E` is triggered.
+pub struct SignalQueue<E> {
+    pending: Vec<QueuedSignal>,
+    _phase: PhantomData<fn() -> E>,
+}
+
+impl<E: Event> Default for SignalQueue<E> {
+    fn default() -> Self {
+        Self {
+            pending: Vec::new(),
+            _phase: PhantomData,
+        }
+    }
+}
+
+impl<E: Event> Pearl for SignalQueue<E> {
+    fn register(source: &mut impl EventSource<Self>) {
+        source.listen::<E>();
+    }
+}
+
+impl<E: Event> Listener<E> for SignalQueue<E> {
+    fn trigger(mut pearl: PearlView<Self>, _: &mut E) {
+        let pending = std::mem::take(&mut pearl.pending);
+        for send in pending {
+            send(pearl.world_mut());
+        }
+    }
+}
+
+impl<E: Event> SignalQueue<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Queues `signal` to be sent the next time `E` is triggered.
+    ///
+    /// The results of its listeners are discarded.
+    pub fn push<T: 'static, R: 'static>(&mut self, signal: Signal<T, R>) {
+        self.pending.push(Box::new(move |world| {
+            world.send_signal(signal);
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use boba_core::{world::Link, World};
+
+    use crate::{SignalBuilder, SignalRegister, MAX_DEFERRED_CHAIN, MAX_SEND_DEPTH};
+
+    use super::*;
+
+    struct Flush;
+
+    struct Receiver(Vec<u32>);
+
+    impl Pearl for Receiver {}
+
+    /// Adds a listener to `builder` that records values,
+    /// and sends the next value using `send` until it reaches `max`.
+    fn echo(
+        world: &mut World,
+        max: u32,
+        send: fn(&mut WorldQueue, Signal<u32>),
+    ) -> (Link<Receiver>, Rc<RefCell<SignalBuilder<u32>>>) {
+        let link = world.insert(Receiver(Vec::new()));
+        let builder = Rc::new(RefCell::new(SignalBuilder::new()));
+        let inner = builder.clone();
+        builder
+            .borrow_mut()
+            .add_listener(link, move |mut pearl, value: &mut u32| {
+                pearl.0.push(*value);
+                if *value < max {
+                    let signal = inner.borrow().build(*value + 1);
+                    send(pearl.world_mut(), signal);
+                }
+            });
+        (link, builder)
+    }
+
+    fn send_now(world: &mut WorldQueue, signal: Signal<u32>) {
+        world.send_signal(signal);
+    }
+
+    fn send_deferred(world: &mut WorldQueue, signal: Signal<u32>) {
+        world.send_signal_deferred(signal);
+    }
+
+    #[test]
+    fn reentrant_send() {
+        let mut world = World::new();
+        let (link, builder) = echo(&mut world, 3, send_now);
+        world.send_signal(builder.borrow().build(1));
+        assert!(world.get(link).unwrap().0 == [1, 2, 3]);
+
+        // a feedback loop is cut off once it gets too deep
+        let (link, builder) = echo(&mut world, u32::MAX, send_now);
+        world.send_signal(builder.borrow().build(1));
+        assert!(world.get(link).unwrap().0.len() == MAX_SEND_DEPTH);
+    }
+
+    #[test]
+    fn deferred_send() {
+        let mut world = World::new();
+        let (link, builder) = echo(&mut world, 3, send_deferred);
+        world.send_signal(builder.borrow().build(1));
+        assert!(world.get(link).unwrap().0 == [1, 2, 3]);
+
+        // a chain of deferred sends runs in a loop, and is cut off once it gets too long
+        let (link, builder) = echo(&mut world, u32::MAX, send_deferred);
+        world.send_signal(builder.borrow().build(1));
+        assert!(world.get(link).unwrap().0.len() == MAX_DEFERRED_CHAIN + 1);
+    }
+
+    #[test]
+    fn queued_send() {
+        let mut world = World::new();
+        world.insert(SignalQueue::<Flush>::new());
+        let (link, builder) = echo(&mut world, 3, |world, signal| {
+            world.queue_signal::<Flush>(signal);
+        });
+
+        assert!(world.queue_signal::<Flush>(builder.borrow().build(1)));
+        assert!(world.get(link).unwrap().0.is_empty());
+
+        // each flush only sends the signals that were queued before it
+        world.trigger(&mut Flush);
+        assert!(world.get(link).unwrap().0 == [1]);
+        world.trigger(&mut Flush);
+        assert!(world.get(link).unwrap().0 == [1, 2]);
+    }
+}
@@ -0,0 +1,273 @@
+use std::{any::TypeId, fmt::Display, rc::Rc};
+
+use boba_core::{
+    world::{Link, LinkRemap, PearlView},
+    Pearl,
+};
+use indexmap::IndexMap;
+
+use crate::{Connection, SignalBuilder};
+
+type ConnectFn<T, R> = Box<dyn Fn(&mut SignalBuilder<T, R>, Link<()>, Rc<str>) -> Connection>;
+
+/// A saved connection between a signal and a named handler on a pearl.
+///
+/// Created using [`SignalBuilder::connections`],
+/// and rebuilt using [`SignalRegistry::rebuild`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignalConnection {
+    pub link: Link<()>,
+    pub handler: String,
+}
+
+/// The error returned when a signal could not be connected to a named handler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignalError {
+    /// No handler is registered with the name.
+    UnknownHandler(String),
+    /// The handler was registered for a different signal type.
+    WrongSignal(String),
+    /// The handler was registered for a different pearl type.
+    WrongPearl(String),
+}
+
+impl Display for SignalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownHandler(name) => write!(f, "signal handler '{name}' is not registered"),
+            Self::WrongSignal(name) => {
+                write!(
+                    f,
+                    "signal handler '{name}' is registered for another signal type"
+                )
+            }
+            Self::WrongPearl(name) => {
+                write!(
+                    f,
+                    "signal handler '{name}' is registered for another pearl type"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SignalError {}
+
+struct Handler {
+    name: Rc<str>,
+    pearl: TypeId,
+    remap: fn(&LinkRemap, Link<()>) -> Option<Link<()>>,
+    /// The [`ConnectFn`] for the signal type the handler was registered with.
+    connect: Box<dyn std::any::Any>,
+}
+
+/// A registry of named signal handlers,
+/// used to connect signals to pearls from data instead of closures.
+///
+/// Connections made through a registry can be saved using [`SignalBuilder::connections`],
+/// and rebuilt on load using [`rebuild`](Self::rebuild).
+#[derive(Default)]
+pub struct SignalRegistry {
+    handlers: IndexMap<String, Handler>,
+}
+
+impl SignalRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.handlers.contains_key(name)
+    }
+
+    /// Registers `handler` with `name`, replacing any handler already registered with that name.
+    pub fn register_signal_handler<P: Pearl, T: 'static, R: 'static>(
+        &mut self,
+        name: impl Into<String>,
+        handler: impl Fn(PearlView<P>, &mut T) -> R + 'static,
+    ) {
+        let handler = Rc::new(handler);
+        let connect: ConnectFn<T, R> = Box::new(move |builder, link, name| {
+            let handler = handler.clone();
+            let listener = move |view: PearlView<P>, data: &mut T| Some(handler(view, data));
+            builder.add_link_listener(link.into_type::<P>(), Some(name), false, listener)
+        });
+
+        let name = name.into();
+        let handler = Handler {
+            name: name.as_str().into(),
+            pearl: TypeId::of::<P>(),
+            remap: |remap, link| Some(remap.get(link.into_type::<P>())?.into_type()),
+            connect: Box::new(connect),
+        };
+        self.handlers.insert(name, handler);
+    }
+
+    /// Connects the handler registered as `name` to the pearl at `link`,
+    /// and returns its [`Connection`].
+    pub fn connect<P: Pearl, T: 'static, R: 'static>(
+        &self,
+        builder: &mut SignalBuilder<T, R>,
+        link: Link<P>,
+        name: &str,
+    ) -> Result<Connection, SignalError> {
+        let handler = self.handler(name)?;
+        if handler.pearl != TypeId::of::<P>() {
+            return Err(SignalError::WrongPearl(name.to_string()));
+        }
+
+        let connect = Self::connect_fn(handler)?;
+        Ok(connect(builder, link.into_type(), handler.name.clone()))
+    }
+
+    /// Adds saved `connections` to `builder`, remapping their links using `remap`.
+    ///
+    /// Connections to links that are not in `remap` are skipped,
+    /// as their pearls were not loaded. Returns the number of connections that were added.
+    ///
+    /// Every connection is checked before any are added,
+    /// so `builder` is unchanged if an error is returned.
+    pub fn rebuild<'a, T: 'static, R: 'static>(
+        &self,
+        builder: &mut SignalBuilder<T, R>,
+        connections: impl IntoIterator<Item = &'a SignalConnection>,
+        remap: &LinkRemap,
+    ) -> Result<usize, SignalError> {
+        let mut remapped = Vec::new();
+        for connection in connections {
+            let handler = self.handler(&connection.handler)?;
+            let connect = Self::connect_fn(handler)?;
+            if let Some(link) = (handler.remap)(remap, connection.link) {
+                remapped.push((connect, link, handler.name.clone()));
+            }
+        }
+
+        for (connect, link, name) in remapped.iter() {
+            connect(builder, *link, name.clone());
+        }
+
+        Ok(remapped.len())
+    }
+
+    fn handler(&self, name: &str) -> Result<&Handler, SignalError> {
+        match self.handlers.get(name) {
+            Some(handler) => Ok(handler),
+            None => Err(SignalError::UnknownHandler(name.to_string())),
+        }
+    }
+
+    fn connect_fn<T: 'static, R: 'static>(
+        handler: &Handler,
+    ) -> Result<&ConnectFn<T, R>, SignalError> {
+        match handler.connect.downcast_ref() {
+            Some(connect) => Ok(connect),
+            None => Err(SignalError::WrongSignal(handler.name.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use boba_core::World;
+
+    use crate::{SignalRegister, WorldSignalExt};
+
+    use super::*;
+
+    struct Door {
+        health: u32,
+    }
+
+    impl Pearl for Door {}
+
+    struct Button;
+
+    impl Pearl for Button {}
+
+    fn registry() -> SignalRegistry {
+        let mut registry = SignalRegistry::new();
+        registry.register_signal_handler(
+            "on_hit",
+            |mut door: PearlView<Door>, damage: &mut u32| {
+                door.health = door.health.saturating_sub(*damage);
+            },
+        );
+        registry
+    }
+
+    #[test]
+    fn save_and_rebuild() {
+        let registry = registry();
+        let mut world = World::new();
+        let door = world.insert(Door { health: 10 });
+        let button = world.insert(Button);
+
+        let mut builder = SignalBuilder::<u32>::new();
+        registry.connect(&mut builder, door, "on_hit").unwrap();
+        builder.add_listener(door, |mut door, _| door.health += 100);
+        assert!(
+            registry.connect(&mut builder, button, "on_hit")
+                == Err(SignalError::WrongPearl("on_hit".into()))
+        );
+        assert!(registry
+            .connect(&mut SignalBuilder::<String>::new(), door, "on_hit")
+            .is_err());
+        assert!(registry.connect(&mut builder, door, "on_open").is_err());
+
+        // only the connections made through the registry are saved
+        let saved = builder.connections();
+        assert!(
+            saved
+                == [SignalConnection {
+                    link: door.into_type(),
+                    handler: "on_hit".into()
+                }]
+        );
+
+        // load the door into a new world and rebuild its connections
+        let mut loaded = World::new();
+        let new_door = loaded.insert(Door { health: 10 });
+        let mut remap = LinkRemap::new();
+        remap.insert(door, new_door);
+
+        let mut builder = SignalBuilder::<u32>::new();
+        assert!(registry.rebuild(&mut builder, &saved, &remap) == Ok(1));
+        assert!(registry.rebuild(&mut builder, &saved, &LinkRemap::new()) == Ok(0));
+        loaded.send_signal(builder.build(3));
+        assert!(loaded.get(new_door).unwrap().health == 7);
+        assert!(builder.connections()[0].link == new_door.into_type());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let registry = registry();
+        let mut world = World::new();
+        let door = world.insert(Door { health: 10 });
+        let mut builder = SignalBuilder::<u32>::new();
+        registry.connect(&mut builder, door, "on_hit").unwrap();
+
+        let json = serde_json::to_string(&builder.connections()).unwrap();
+        let saved: Vec<SignalConnection> = serde_json::from_str(&json).unwrap();
+        assert!(saved == builder.connections());
+
+        let mut loaded = World::new();
+        let new_door = loaded.insert(Door { health: 10 });
+        let mut remap = LinkRemap::new();
+        remap.insert(door, new_door);
+
+        let mut builder = SignalBuilder::<u32>::new();
+        assert!(registry.rebuild(&mut builder, &saved, &remap) == Ok(1));
+        loaded.send_signal(builder.build(4));
+        assert!(loaded.get(new_door).unwrap().health == 6);
+    }
+}
@@ -1,89 +1,939 @@
-use std::rc::Rc;
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashSet, VecDeque},
+    rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 use boba_core::{
+    pearl::Event,
     world::{Link, PearlView, WorldQueue},
     Pearl, World,
 };
 use extension_trait::extension_trait;
-use indexmap::IndexSet;
+use indexmap::IndexMap;
+
+use crate::{SignalAdapter, SignalConnection, SignalFuture, SignalQueue};
+
+pub type Listener<P, T, R = ()> = fn(PearlView<P>, &mut T) -> R;
+/// Sends a message to a listener, passing each result to the collector
+/// until the collector returns `false`.
+pub(crate) type Sender<T, R> =
+    Rc<dyn Fn(&mut WorldQueue, &mut T, &mut dyn FnMut(R) -> bool) -> Reach>;
+pub(crate) type DeadSet = Rc<RefCell<HashSet<Connection>>>;
+/// A listener captured by a signal, along with the link it was added for.
+type Target<T, R> = (Connection, Option<Link<()>>, Sender<T, R>);
+
+type DeferredSend = Box<dyn FnOnce(&mut WorldQueue)>;
+
+/// The most times the signals of one builder may be sent inside each other.
+pub const MAX_SEND_DEPTH: usize = 32;
+/// The most signals a chain of deferred sends may send,
+/// where the listeners of each deferred signal defer more signals.
+pub const MAX_DEFERRED_CHAIN: usize = 1024;
+
+static NEXT_BUILDER_ID: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// The ids of the builders whose signals are currently being sent.
+    static SENDING: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+    /// The deferred signals waiting to be sent by the chain that is currently being drained.
+    static DEFERRED: RefCell<Option<VecDeque<DeferredSend>>> = const { RefCell::new(None) };
+}
+
+/// Removes a builder id from the sending stack when a send finishes or unwinds.
+struct SendGuard;
+
+impl Drop for SendGuard {
+    fn drop(&mut self) {
+        SENDING.with_borrow_mut(|sending| sending.pop());
+    }
+}
+
+/// Ends the deferred chain that is being drained when it finishes or unwinds.
+struct DrainGuard;
+
+impl Drop for DrainGuard {
+    fn drop(&mut self) {
+        DEFERRED.take();
+    }
+}
+
+/// Adds `send` to the deferred chain that is being drained.
+///
+/// Returns `send` back if there is no chain being drained.
+fn join_chain(send: DeferredSend) -> Option<DeferredSend> {
+    DEFERRED.with_borrow_mut(|deferred| match deferred {
+        Some(chain) => {
+            chain.push_back(send);
+            None
+        }
+        None => Some(send),
+    })
+}
+
+/// Sends `send` followed by every signal deferred while sending it.
+///
+/// Deferred signals are sent one after another in a loop
+/// instead of in nested queue flushes, so long chains cannot overflow the stack.
+/// A chain that keeps deferring signals is stopped and logged after [`MAX_DEFERRED_CHAIN`] sends.
+fn drain_deferred(world: &mut World, send: DeferredSend) {
+    let Some(send) = join_chain(send) else {
+        return;
+    };
+
+    DEFERRED.set(Some(VecDeque::new()));
+    let _guard = DrainGuard;
+    let mut next = Some(send);
+    let mut sent = 0;
+    while let Some(send) = next {
+        if sent == MAX_DEFERRED_CHAIN {
+            let remaining = DEFERRED.take().map_or(0, |chain| chain.len()) + 1;
+            log::error!(
+                "Deferred signals kept deferring more signals after {sent} sends. \
+                The remaining {remaining} signals were dropped."
+            );
+            return;
+        }
+
+        send(&mut WorldQueue::new(world));
+        sent += 1;
+        next = DEFERRED.with_borrow_mut(|deferred| deferred.as_mut()?.pop_front());
+    }
+}
 
-pub type Listener<P, T> = fn(PearlView<P>, &mut T);
-pub(crate) type Sender<T> = Rc<dyn Fn(&mut WorldQueue, &mut T)>;
+/// A token for a single listener added to a [`SignalBuilder`].
+///
+/// Used to [`disconnect`](SignalRegister::disconnect) that listener
+/// without affecting any other listeners on the same link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Connection(u64);
 
-pub struct SignalBuilder<T: 'static> {
-    links: IndexSet<Link<()>>,
-    listeners: Vec<Sender<T>>,
+/// The outcome of sending a message to a single listener.
+pub(crate) enum Reach {
+    /// The listener will never be reached again,
+    /// because its pearl no longer exists or it only fires once.
+    Dead,
+    Continue,
+    /// The collector asked for dispatch to end.
+    Stop,
+    /// A listener that only fires once has fired, and the collector asked for dispatch to end.
+    DeadStop,
 }
 
-impl<T: 'static> Default for SignalBuilder<T> {
+impl Reach {
+    fn from_collected(keep_going: bool) -> Self {
+        match keep_going {
+            true => Self::Continue,
+            false => Self::Stop,
+        }
+    }
+
+    fn from_last(keep_going: bool) -> Self {
+        match keep_going {
+            true => Self::Dead,
+            false => Self::DeadStop,
+        }
+    }
+}
+
+struct Entry<T, R> {
+    /// The link this listener targets, or `None` for a type listener.
+    link: Option<Link<()>>,
+    /// The name of the registered handler this listener was created from.
+    handler: Option<Rc<str>>,
+    alive: fn(&World, Link<()>) -> bool,
+    sender: Sender<T, R>,
+}
+
+impl<T, R> Entry<T, R> {
+    fn is_alive(&self, world: &World) -> bool {
+        self.link.is_none_or(|link| (self.alive)(world, link))
+    }
+}
+
+/// A collection of listeners used to build [`Signal`]s.
+///
+/// Listeners whose pearls no longer exist are pruned automatically.
+/// Signals report the listeners they could not reach back to their builder,
+/// and those listeners are removed the next time the builder is modified.
+/// Listeners can also be pruned immediately using [`prune`](Self::prune).
+///
+/// Listeners return a value of type `R`, which is collected when the signal is sent.
+/// By default `R` is `()`, for signals that only notify their listeners.
+pub struct SignalBuilder<T: 'static, R: 'static = ()> {
+    id: u64,
+    next_id: u64,
+    listeners: IndexMap<Connection, Entry<T, R>>,
+    dead: DeadSet,
+}
+
+impl<T: 'static, R: 'static> Default for SignalBuilder<T, R> {
     fn default() -> Self {
         Self {
-            links: IndexSet::new(),
-            listeners: Vec::new(),
+            id: NEXT_BUILDER_ID.fetch_add(1, Ordering::Relaxed),
+            next_id: 0,
+            listeners: IndexMap::new(),
+            dead: DeadSet::default(),
         }
     }
 }
 
-impl<T: 'static> SignalBuilder<T> {
+impl<T: 'static, R: 'static> SignalBuilder<T, R> {
     pub fn new() -> Self {
         Self::default()
     }
 
-    pub fn build(&self, message: T) -> Signal<T> {
+    /// Returns the number of listeners that are still live in `world`,
+    /// not including listeners whose pearls no longer exist or that a signal has failed to reach.
+    pub fn len(&self, world: &World) -> usize {
+        let dead = self.dead.borrow();
+        let listeners = self.listeners.iter();
+        listeners
+            .filter(|(connection, entry)| !dead.contains(*connection) && entry.is_alive(world))
+            .count()
+    }
+
+    /// Returns `true` if there are no listeners that are still live in `world`.
+    pub fn is_empty(&self, world: &World) -> bool {
+        self.len(world) == 0
+    }
+
+    pub fn is_connected(&self, connection: Connection) -> bool {
+        self.listeners.contains_key(&connection) && !self.dead.borrow().contains(&connection)
+    }
+
+    /// Removes every listener whose pearl no longer exists in `world`,
+    /// and returns the number of listeners that were removed.
+    pub fn prune(&mut self, world: &World) -> usize {
+        self.remove_dead();
+        let len = self.listeners.len();
+        self.listeners.retain(|_, entry| entry.is_alive(world));
+        len - self.listeners.len()
+    }
+
+    pub fn build(&self, message: T) -> Signal<T, R> {
+        let dead = self.dead.borrow();
+        let listeners = self.listeners.iter();
         Signal {
+            source: self.id,
             message,
-            listeners: self.listeners.clone(),
+            listeners: listeners
+                .filter(|(connection, _)| !dead.contains(*connection))
+                .map(|(connection, entry)| (*connection, entry.link, entry.sender.clone()))
+                .collect(),
+            dead: self.dead.clone(),
+        }
+    }
+
+    /// Returns a future that resolves to a clone of the message of the next signal built and sent.
+    ///
+    /// The future does not produce a result for the signal,
+    /// and its listener is removed once it has fired or the future is dropped.
+    pub fn next_future(&mut self) -> SignalFuture<T>
+    where
+        T: Clone,
+    {
+        let dead = self.dead.clone();
+        SignalFuture::new(dead, |sender| {
+            self.insert_entry(Entry {
+                link: None,
+                handler: None,
+                alive: |_, _| true,
+                sender,
+            })
+        })
+    }
+
+    /// Returns the connections created by a [`SignalRegistry`](crate::SignalRegistry),
+    /// in the order they were added.
+    ///
+    /// Listeners added as closures cannot be saved, so they are not included.
+    pub fn connections(&self) -> Vec<SignalConnection> {
+        let dead = self.dead.borrow();
+        let listeners = self.listeners.iter();
+        listeners
+            .filter(|(connection, _)| !dead.contains(*connection))
+            .filter_map(|(_, entry)| {
+                Some(SignalConnection {
+                    link: entry.link?,
+                    handler: entry.handler.as_deref()?.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Adds `listener` for the pearl at `link`, recording the name of the handler it was created from.
+    ///
+    /// The listener returns `None` if it did not fire, in which case nothing is collected.
+    /// If `once` is set, the listener is disconnected after it first fires.
+    pub(crate) fn add_link_listener<P: Pearl>(
+        &mut self,
+        link: Link<P>,
+        handler: Option<Rc<str>>,
+        once: bool,
+        listener: impl Fn(PearlView<P>, &mut T) -> Option<R> + 'static,
+    ) -> Connection {
+        // signals built before a once listener fired may still try to reach it
+        let fired = Cell::new(false);
+        let sender: Sender<T, R> = Rc::new(move |world, data, collect| {
+            if fired.get() {
+                return Reach::Dead;
+            }
+
+            let Some(view) = PearlView::new(link, world) else {
+                return Reach::Dead;
+            };
+
+            let Some(result) = listener(view, data) else {
+                return Reach::Continue;
+            };
+
+            let keep_going = collect(result);
+            match once {
+                false => Reach::from_collected(keep_going),
+                true => {
+                    fired.set(true);
+                    Reach::from_last(keep_going)
+                }
+            }
+        });
+
+        let entry = Entry {
+            link: Some(link.into_type()),
+            handler,
+            alive: |world, link| world.contains(link.into_type::<P>()),
+            sender,
+        };
+        self.insert_entry(entry)
+    }
+
+    fn insert_entry(&mut self, entry: Entry<T, R>) -> Connection {
+        self.remove_dead();
+        let connection = Connection(self.next_id);
+        self.next_id += 1;
+        self.listeners.insert(connection, entry);
+        connection
+    }
+
+    /// Removes the listeners that signals have reported as dead.
+    fn remove_dead(&mut self) {
+        // removed listeners may own futures that mark themselves as dead when dropped
+        let dead: Vec<_> = self.dead.borrow_mut().drain().collect();
+        for connection in dead {
+            self.listeners.shift_remove(&connection);
         }
     }
 }
 
 #[extension_trait]
-pub impl<T: 'static> SignalRegister<T> for SignalBuilder<T> {
-    fn add_listener<P: Pearl>(&mut self, link: Link<P>, listener: Listener<P, T>) {
-        let (index, _) = self.links.insert_full(link.into_type());
-        self.listeners.insert(
-            index,
-            Rc::new(move |world, data| {
-                if let Some(view) = PearlView::new(link, world) {
-                    listener(view, data);
+pub impl<T: 'static, R: 'static> SignalRegister<T, R> for SignalBuilder<T, R> {
+    /// Adds `listener` for the pearl at `link`, and returns its [`Connection`].
+    ///
+    /// A link may have any number of listeners, which are called in the order they were added.
+    fn add_listener<P: Pearl>(
+        &mut self,
+        link: Link<P>,
+        listener: impl Fn(PearlView<P>, &mut T) -> R + 'static,
+    ) -> Connection {
+        self.add_link_listener(link, None, false, move |view, data| {
+            Some(listener(view, data))
+        })
+    }
+
+    /// Adds `listener` for the pearl at `link` that is disconnected after it is first called.
+    fn add_once_listener<P: Pearl>(
+        &mut self,
+        link: Link<P>,
+        listener: impl Fn(PearlView<P>, &mut T) -> R + 'static,
+    ) -> Connection {
+        self.add_link_listener(link, None, true, move |view, data| {
+            Some(listener(view, data))
+        })
+    }
+
+    /// Relays every message to the signal of the pearl at `link`, transformed using `map`.
+    ///
+    /// The signal is selected from the pearl using `signal`, and is sent immediately.
+    /// Its results are discarded, and this listener does not produce a result.
+    fn forward_to<P: Pearl, U: 'static, R2: 'static>(
+        &mut self,
+        link: Link<P>,
+        signal: impl Fn(&P) -> &SignalBuilder<U, R2> + 'static,
+        map: impl Fn(&T) -> U + 'static,
+    ) -> Connection {
+        SignalAdapter::new(self).forward_to(link, signal, map)
+    }
+
+    /// Returns an adapter whose listeners are only called for messages that pass `filter`.
+    fn filter(&mut self, filter: impl Fn(&T) -> bool + 'static) -> SignalAdapter<'_, T, R, T> {
+        SignalAdapter::new(self).filter(filter)
+    }
+
+    /// Returns an adapter whose listeners receive messages transformed using `map`.
+    fn map<U: 'static>(&mut self, map: impl Fn(&T) -> U + 'static) -> SignalAdapter<'_, T, R, U> {
+        SignalAdapter::new(self).map(map)
+    }
+
+    /// Adds `listener` for every pearl of type `P`, and returns its [`Connection`].
+    ///
+    /// The pearls are collected when the signal is sent,
+    /// so pearls inserted after the listener was added will also receive the signal.
+    fn add_type_listener<P: Pearl>(
+        &mut self,
+        listener: impl Fn(PearlView<P>, &mut T) -> R + 'static,
+    ) -> Connection {
+        self.add_type_listener_filtered(|_: &P| true, listener)
+    }
+
+    /// Adds `listener` for every pearl of type `P` that passes `filter`, and returns its [`Connection`].
+    ///
+    /// The filter is checked for each pearl right before its listener is called.
+    fn add_type_listener_filtered<P: Pearl>(
+        &mut self,
+        filter: impl Fn(&P) -> bool + 'static,
+        listener: impl Fn(PearlView<P>, &mut T) -> R + 'static,
+    ) -> Connection {
+        let sender: Sender<T, R> = Rc::new(move |world, data, collect| {
+            for link in world.links_copied::<P>() {
+                // earlier listeners may have removed this pearl
+                let Some(view) = PearlView::new(link, world) else {
+                    continue;
+                };
+
+                if filter(&view) && !collect(listener(view, data)) {
+                    return Reach::Stop;
                 }
-            }),
-        );
+            }
+
+            Reach::Continue
+        });
+
+        let entry = Entry {
+            link: None,
+            handler: None,
+            alive: |_, _| true,
+            sender,
+        };
+        self.insert_entry(entry)
     }
 
+    /// Removes every listener for the pearl at `link`.
+    ///
+    /// Returns `false` if there were no listeners for `link`.
     fn remove_listener<P: Pearl>(&mut self, link: &Link<P>) -> bool {
-        let Some((index, _)) = self.links.shift_remove_full(&link.into_type()) else {
-            return false;
-        };
+        self.remove_dead();
+        let len = self.listeners.len();
+        let link = Some(link.into_type());
+        self.listeners.retain(|_, entry| entry.link != link);
+        self.listeners.len() != len
+    }
 
-        self.listeners.remove(index);
-        true
+    /// Removes the single listener for `connection`.
+    ///
+    /// Returns `false` if it was already disconnected.
+    fn disconnect(&mut self, connection: Connection) -> bool {
+        self.remove_dead();
+        self.listeners.shift_remove(&connection).is_some()
     }
 }
 
-pub struct Signal<T: 'static> {
+pub struct Signal<T: 'static, R: 'static = ()> {
+    source: u64,
     message: T,
-    listeners: Vec<Sender<T>>,
+    listeners: Vec<Target<T, R>>,
+    dead: DeadSet,
 }
 
-impl<T: 'static> Signal<T> {
-    pub fn send(mut self, world: &mut WorldQueue) {
-        for listener in self.listeners {
-            listener(world, &mut self.message);
+impl<T: 'static, R: 'static> Signal<T, R> {
+    /// Sends the message to every listener and returns their results in the order they were called.
+    ///
+    /// Any listeners whose pearls no longer exist are reported back to the builder.
+    ///
+    /// Listeners may send signals from their own builder,
+    /// but once the signals of one builder are nested [`MAX_SEND_DEPTH`] deep
+    /// the send is treated as a feedback loop. It is dropped and an error is logged,
+    /// and no results are collected.
+    /// Use [`send_signal_deferred`](WorldSignalExt::send_signal_deferred)
+    /// to send it after the current dispatch instead.
+    pub fn send(self, world: &mut WorldQueue) -> Vec<R> {
+        let mut results = Vec::new();
+        self.dispatch(world, |result| {
+            results.push(result);
+            true
+        });
+        results
+    }
+
+    /// Sends the message only to the listeners added for the pearl at `link`,
+    /// and returns their results.
+    ///
+    /// Type listeners are not included, even if `link` is for a pearl of their type.
+    pub fn send_to<P: Pearl>(mut self, world: &mut WorldQueue, link: Link<P>) -> Vec<R> {
+        let link = Some(link.into_type());
+        self.listeners.retain(|(_, target, _)| *target == link);
+        self.send(world)
+    }
+
+    /// Sends the message to each listener until `stop` returns `true` for a result.
+    ///
+    /// The returned results include the result that stopped the dispatch.
+    pub fn send_until(self, world: &mut WorldQueue, mut stop: impl FnMut(&R) -> bool) -> Vec<R> {
+        let mut results = Vec::new();
+        self.dispatch(world, |result| {
+            let keep_going = !stop(&result);
+            results.push(result);
+            keep_going
+        });
+        results
+    }
+
+    /// Sends the message to every listener, and combines their results using `f`.
+    pub fn fold<A>(self, world: &mut WorldQueue, init: A, mut f: impl FnMut(A, R) -> A) -> A {
+        let mut acc = Some(init);
+        self.dispatch(world, |result| {
+            acc = Some(f(acc.take().unwrap(), result));
+            true
+        });
+        acc.unwrap()
+    }
+
+    /// Sends the message to each listener, passing every result to `collect`
+    /// until it returns `false`.
+    fn dispatch(mut self, world: &mut WorldQueue, mut collect: impl FnMut(R) -> bool) {
+        let looping = SENDING.with_borrow_mut(|sending| {
+            let depth = sending.iter().filter(|id| **id == self.source).count();
+            if depth >= MAX_SEND_DEPTH {
+                return true;
+            }
+
+            sending.push(self.source);
+            false
+        });
+
+        if looping {
+            let name = std::any::type_name::<T>();
+            log::error!(
+                "Signal<{name}> was sent by its own listeners {MAX_SEND_DEPTH} times in a row. \
+                The signal was dropped to break the feedback loop."
+            );
+            return;
         }
+
+        let _guard = SendGuard;
+        for (connection, _, sender) in self.listeners {
+            match sender(world, &mut self.message, &mut collect) {
+                Reach::Continue => (),
+                Reach::Stop => break,
+                Reach::Dead => {
+                    self.dead.borrow_mut().insert(connection);
+                }
+                Reach::DeadStop => {
+                    self.dead.borrow_mut().insert(connection);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl<T: 'static> Signal<T, bool> {
+    /// Returns `true` if every listener returns `true`.
+    ///
+    /// Dispatch ends at the first listener that returns `false`, allowing any listener to veto.
+    pub fn all(self, world: &mut WorldQueue) -> bool {
+        let mut all = true;
+        self.dispatch(world, |result| {
+            all = result;
+            result
+        });
+        all
+    }
+
+    /// Returns `true` if any listener returns `true`.
+    ///
+    /// Dispatch ends at the first listener that returns `true`.
+    pub fn any(self, world: &mut WorldQueue) -> bool {
+        let mut any = false;
+        self.dispatch(world, |result| {
+            any = result;
+            !result
+        });
+        any
     }
 }
 
 #[extension_trait]
-pub impl<T: 'static> WorldSignalExt<T> for WorldQueue<'_> {
-    fn send_signal(&mut self, signal: Signal<T>) {
+pub impl<T: 'static, R: 'static> WorldSignalExt<T, R> for WorldQueue<'_> {
+    /// Sends `signal` and returns the results of its listeners.
+    fn send_signal(&mut self, signal: Signal<T, R>) -> Vec<R> {
         signal.send(self)
     }
+
+    /// Sends `signal` once the current dispatch has finished and the queue is flushed.
+    ///
+    /// Signals deferred by the listeners of a deferred signal are sent in turn afterwards.
+    /// A chain that keeps deferring signals is stopped and logged after [`MAX_DEFERRED_CHAIN`] sends.
+    /// The results of its listeners are discarded.
+    fn send_signal_deferred(&mut self, signal: Signal<T, R>) {
+        let send: DeferredSend = Box::new(move |world| {
+            world.send_signal(signal);
+        });
+
+        if let Some(send) = join_chain(send) {
+            self.defer(move |world| drain_deferred(world, send));
+        }
+    }
+
+    /// Queues `signal` in the first [`SignalQueue`] for the event `E`,
+    /// to be sent the next time `E` is triggered.
+    ///
+    /// Returns `false` if there is no queue for `E`, in which case the signal is dropped.
+    fn queue_signal<E: Event>(&mut self, signal: Signal<T, R>) -> bool {
+        let Some(link) = self.links_copied::<SignalQueue<E>>().next() else {
+            return false;
+        };
+
+        self.get_mut(link).unwrap().push(signal);
+        true
+    }
 }
 
-impl<T: 'static> WorldSignalExt<T> for World {
-    fn send_signal(&mut self, signal: Signal<T>) {
+impl<T: 'static, R: 'static> WorldSignalExt<T, R> for World {
+    fn send_signal(&mut self, signal: Signal<T, R>) -> Vec<R> {
         let mut queue = WorldQueue::new(self);
-        queue.send_signal(signal);
+        queue.send_signal(signal)
+    }
+
+    /// Sends `signal` immediately, as no dispatch can be running on a world,
+    /// unless a chain of deferred signals is being sent, in which case it joins the chain.
+    fn send_signal_deferred(&mut self, signal: Signal<T, R>) {
+        drain_deferred(
+            self,
+            Box::new(move |world| {
+                world.send_signal(signal);
+            }),
+        );
+    }
+
+    fn queue_signal<E: Event>(&mut self, signal: Signal<T, R>) -> bool {
+        WorldQueue::new(self).queue_signal::<E>(signal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Receiver(Vec<u32>);
+
+    impl Pearl for Receiver {}
+
+    fn received(world: &World, link: Link<Receiver>) -> &[u32] {
+        &world.get(link).unwrap().0
+    }
+
+    #[test]
+    fn add_and_send() {
+        let mut world = World::new();
+        let a = world.insert(Receiver(Vec::new()));
+        let b = world.insert(Receiver(Vec::new()));
+
+        let mut builder = SignalBuilder::new();
+        let offset = 10;
+        builder.add_listener(a, move |mut pearl, value: &mut u32| {
+            pearl.0.push(*value + offset)
+        });
+        builder.add_listener(b, |mut pearl, value| pearl.0.push(*value));
+        assert!(builder.len(&world) == 2);
+
+        world.send_signal(builder.build(1));
+        assert!(received(&world, a) == [11]);
+        assert!(received(&world, b) == [1]);
+    }
+
+    #[test]
+    fn remove_listeners() {
+        let mut world = World::new();
+        let a = world.insert(Receiver(Vec::new()));
+        let b = world.insert(Receiver(Vec::new()));
+
+        let mut builder = SignalBuilder::new();
+        builder.add_listener(a, |mut pearl, value: &mut u32| pearl.0.push(*value));
+        builder.add_listener(b, |mut pearl, value| pearl.0.push(*value));
+        assert!(builder.remove_listener(&a));
+        assert!(!builder.remove_listener(&a));
+
+        world.send_signal(builder.build(1));
+        assert!(received(&world, a).is_empty());
+        assert!(received(&world, b) == [1]);
+    }
+
+    #[test]
+    fn duplicate_links() {
+        let mut world = World::new();
+        let a = world.insert(Receiver(Vec::new()));
+        let b = world.insert(Receiver(Vec::new()));
+
+        let mut builder = SignalBuilder::new();
+        let first = builder.add_listener(a, |mut pearl, value: &mut u32| pearl.0.push(*value));
+        builder.add_listener(b, |mut pearl, value| pearl.0.push(*value));
+        let second = builder.add_listener(a, |mut pearl, value| pearl.0.push(*value * 2));
+        assert!(first != second);
+
+        world.send_signal(builder.build(1));
+        assert!(received(&world, a) == [1, 2]);
+
+        // disconnecting one listener leaves the other listeners on the same link
+        assert!(builder.disconnect(first));
+        assert!(!builder.disconnect(first));
+        assert!(builder.is_connected(second));
+        world.send_signal(builder.build(3));
+        assert!(received(&world, a) == [1, 2, 6]);
+        assert!(received(&world, b) == [1, 3]);
+
+        assert!(builder.remove_listener(&a));
+        assert!(builder.len(&world) == 1);
+    }
+
+    #[test]
+    fn prune_dead() {
+        let mut world = World::new();
+        let a = world.insert(Receiver(Vec::new()));
+        let b = world.insert(Receiver(Vec::new()));
+        let c = world.insert(Receiver(Vec::new()));
+
+        let mut builder = SignalBuilder::new();
+        builder.add_listener(a, |mut pearl, value: &mut u32| pearl.0.push(*value));
+        builder.add_listener(b, |mut pearl, value| pearl.0.push(*value));
+        let dead = builder.add_listener(c, |mut pearl, value| pearl.0.push(*value));
+
+        // sending reports dead listeners back to the builder
+        world.remove(c);
+        world.send_signal(builder.build(1));
+        assert!(builder.len(&world) == 2);
+        assert!(!builder.is_connected(dead));
+
+        // removed pearls are not counted even before they are pruned
+        world.remove(b);
+        assert!(builder.len(&world) == 1);
+
+        // pruning removes listeners before they are sent to
+        assert!(builder.prune(&world) == 1);
+        assert!(builder.len(&world) == 1);
+        world.send_signal(builder.build(2));
+        assert!(received(&world, a) == [1, 2]);
+    }
+
+    #[test]
+    fn type_listeners() {
+        let mut world = World::new();
+        let a = world.insert(Receiver(Vec::new()));
+
+        let mut builder = SignalBuilder::new();
+        builder.add_type_listener(|mut pearl: PearlView<Receiver>, value: &mut u32| {
+            pearl.0.push(*value)
+        });
+        builder.add_type_listener_filtered(
+            |pearl: &Receiver| pearl.0.len() > 1,
+            |mut pearl, value| pearl.0.push(*value * 10),
+        );
+
+        // pearls inserted after the listener was added also receive the signal
+        let b = world.insert(Receiver(vec![0]));
+        world.send_signal(builder.build(1));
+        assert!(received(&world, a) == [1]);
+        assert!(received(&world, b) == [0, 1, 10]);
+
+        world.remove(a);
+        assert!(builder.prune(&world) == 0);
+        world.send_signal(builder.build(2));
+        assert!(received(&world, b) == [0, 1, 10, 2, 20]);
+    }
+
+    #[test]
+    fn returned_values() {
+        let mut world = World::new();
+        let a = world.insert(Receiver(vec![1]));
+        let b = world.insert(Receiver(vec![2]));
+        let c = world.insert(Receiver(vec![3]));
+
+        let mut builder = SignalBuilder::new();
+        for link in [a, b, c] {
+            builder.add_listener(link, |mut pearl, value: &mut u32| {
+                pearl.0.push(*value);
+                pearl.0[0] * *value
+            });
+        }
+
+        assert!(world.send_signal(builder.build(2)) == [2, 4, 6]);
+        let sum = builder
+            .build(1)
+            .fold(&mut WorldQueue::new(&mut world), 0, |a, b| a + b);
+        assert!(sum == 6);
+
+        // stopping early skips the remaining listeners
+        let results = builder
+            .build(3)
+            .send_until(&mut WorldQueue::new(&mut world), |result| *result > 5);
+        assert!(results == [3, 6]);
+        assert!(received(&world, c) == [3, 2, 1]);
+    }
+
+    #[test]
+    fn veto() {
+        let mut world = World::new();
+        let a = world.insert(Receiver(vec![1]));
+        let b = world.insert(Receiver(vec![0]));
+        let c = world.insert(Receiver(vec![1]));
+
+        let mut builder = SignalBuilder::<(), bool>::new();
+        for link in [a, b, c] {
+            builder.add_listener(link, |mut pearl, _| {
+                pearl.0.push(0);
+                pearl.0[0] == 1
+            });
+        }
+
+        let mut queue = WorldQueue::new(&mut world);
+        assert!(!builder.build(()).all(&mut queue));
+        assert!(builder.build(()).any(&mut queue));
+        drop(queue);
+
+        assert!(received(&world, a) == [1, 0, 0]);
+        assert!(received(&world, b) == [0, 0]);
+        assert!(received(&world, c) == [1]);
+    }
+
+    #[test]
+    fn await_signal() {
+        use std::{
+            future::Future,
+            pin::pin,
+            sync::{
+                atomic::{AtomicBool, Ordering},
+                Arc,
+            },
+            task::{Context, Poll, Wake, Waker},
+        };
+
+        #[derive(Default)]
+        struct Flag(AtomicBool);
+
+        impl Wake for Flag {
+            fn wake(self: Arc<Self>) {
+                self.0.store(true, Ordering::Relaxed);
+            }
+        }
+
+        let mut world = World::new();
+        let mut builder = SignalBuilder::<u32>::new();
+        let mut future = pin!(builder.next_future());
+        assert!(builder.len(&world) == 1);
+
+        let flag = Arc::new(Flag::default());
+        let waker = Waker::from(flag.clone());
+        let mut context = Context::from_waker(&waker);
+        assert!(future.as_mut().poll(&mut context).is_pending());
+
+        world.send_signal(builder.build(7));
+        assert!(flag.0.load(Ordering::Relaxed));
+        assert!(future.as_mut().poll(&mut context) == Poll::Ready(7));
+
+        // the one-shot listener is removed after firing
+        assert!(builder.is_empty(&world));
+        world.send_signal(builder.build(8));
+        assert!(future.as_mut().poll(&mut context).is_pending());
+
+        // dropping a future removes its listener without sending
+        let dropped = builder.next_future();
+        assert!(builder.len(&world) == 1);
+        drop(dropped);
+        assert!(builder.is_empty(&world));
+    }
+
+    #[test]
+    fn drop_future() {
+        let mut world = World::new();
+        let mut builder = SignalBuilder::<u32>::new();
+
+        // futures that have fired are not reported as dead again when dropped
+        let fired = builder.next_future();
+        world.send_signal(builder.build(1));
+        assert!(builder.prune(&world) == 0);
+        drop(fired);
+        assert!(builder.dead.borrow().is_empty());
+
+        // a pending future may be dropped along with the listener that owns it
+        let a = world.insert(Receiver(Vec::new()));
+        let owned = Rc::new(RefCell::new(None));
+        builder.add_listener(a, {
+            let owned = owned.clone();
+            move |_, _| drop(owned.borrow())
+        });
+        let signal = builder.build(2);
+        *owned.borrow_mut() = Some(builder.next_future());
+        drop(owned);
+
+        world.remove(a);
+        world.send_signal(signal);
+        assert!(builder.prune(&world) == 0);
+        assert!(builder.is_empty(&world));
+    }
+
+    #[test]
+    fn once_and_send_to() {
+        let mut world = World::new();
+        let a = world.insert(Receiver(Vec::new()));
+        let b = world.insert(Receiver(Vec::new()));
+
+        let mut builder = SignalBuilder::new();
+        builder.add_once_listener(a, |mut pearl, value: &mut u32| pearl.0.push(*value));
+        builder.add_listener(b, |mut pearl, value| pearl.0.push(*value));
+
+        // signals built before the once listener fired cannot call it again
+        let early = builder.build(0);
+        world.send_signal(builder.build(1));
+        world.send_signal(early);
+        assert!(received(&world, a) == [1]);
+        assert!(received(&world, b) == [1, 0]);
+        assert!(builder.len(&world) == 1);
+
+        builder.add_listener(a, |mut pearl, value| pearl.0.push(*value * 10));
+        builder
+            .build(2)
+            .send_to(&mut WorldQueue::new(&mut world), a);
+        assert!(received(&world, a) == [1, 20]);
+        assert!(received(&world, b) == [1, 0]);
+    }
+
+    #[test]
+    fn forward_and_adapt() {
+        struct Relay(SignalBuilder<String>);
+
+        impl Pearl for Relay {}
+
+        let mut world = World::new();
+        let a = world.insert(Receiver(Vec::new()));
+        let mut relay = Relay(SignalBuilder::new());
+        relay
+            .0
+            .add_listener(a, |mut pearl, message| pearl.0.push(message.len() as u32));
+        let relay = world.insert(relay);
+
+        let mut builder = SignalBuilder::<u32>::new();
+        builder.forward_to(
+            relay,
+            |relay: &Relay| &relay.0,
+            |value| "x".repeat(*value as usize),
+        );
+        builder
+            .filter(|value| *value % 2 == 0)
+            .map(|value| *value * 100)
+            .add_listener(a, |mut pearl, value| pearl.0.push(*value));
+
+        world.send_signal(builder.build(1));
+        world.send_signal(builder.build(2));
+        assert!(received(&world, a) == [1, 2, 200]);
     }
 }
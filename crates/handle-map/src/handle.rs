@@ -4,9 +4,12 @@ use std::{
     marker::PhantomData,
 };
 
+/// A typed id for a value in a handle map.
+///
+/// Handles are just ids, so they are always [`Send`] and [`Sync`] regardless of `T`.
 pub struct Handle<T> {
     raw: u64,
-    _type: PhantomData<*const T>,
+    _type: PhantomData<fn() -> T>,
 }
 
 impl<T> Hash for Handle<T> {
@@ -19,10 +22,7 @@ impl<T> Copy for Handle<T> {}
 impl<T> Clone for Handle<T> {
     #[inline]
     fn clone(&self) -> Self {
-        Self {
-            raw: self.raw.clone(),
-            _type: PhantomData,
-        }
+        *self
     }
 }
 
@@ -11,7 +11,7 @@ use super::{sparse::SparseHandleMap, HandleMapId};
 /// This means that when accessing with a handle, there needs to be one more level of indirection under the covers.
 /// However, iteration over the map will always be maximally efficient,
 /// as the whole map can be used as a tightly packed array slice.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DenseHandleMap<T> {
     id: u16,
     link_map: SparseHandleMap<usize>,
@@ -71,6 +71,22 @@ impl<T> DenseHandleMap<T> {
         self.values.is_empty()
     }
 
+    /// Returns the number of items the map can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.values.capacity()
+    }
+
+    /// Returns the number of bytes allocated by the map.
+    ///
+    /// This does not include any heap memory owned by the items themselves.
+    #[inline]
+    pub fn allocated_bytes(&self) -> usize {
+        self.link_map.allocated_bytes()
+            + self.back_link.capacity() * std::mem::size_of::<Handle<T>>()
+            + self.values.capacity() * std::mem::size_of::<T>()
+    }
+
     /// Returns the handle that will be provided after `count` inserts.
     ///
     /// Is only true for chains of inserts.
@@ -113,6 +129,22 @@ impl<T> DenseHandleMap<T> {
         Some(&mut self.values[*index])
     }
 
+    /// Returns mutable references to the data associated with each handle in `handles`.
+    ///
+    /// Returns `None` if any handle is invalid, or if any two handles are the same.
+    #[inline]
+    pub fn get_disjoint_mut<const N: usize>(
+        &mut self,
+        handles: [Handle<T>; N],
+    ) -> Option<[&mut T; N]> {
+        let mut indices = [0; N];
+        for (index, handle) in indices.iter_mut().zip(handles) {
+            *index = *self.link_map.get(handle.into_type())?;
+        }
+
+        self.values.get_disjoint_mut(indices).ok()
+    }
+
     /// Removes and returns the data associated with `handle` from this map.
     ///
     /// Returns `None` if the handle is invalid.
@@ -135,6 +167,52 @@ impl<T> DenseHandleMap<T> {
         Some(self.values.swap_remove(index))
     }
 
+    /// Replaces the contents of this map with a clone of the contents of `other`.
+    ///
+    /// Handles that are valid for `other` become valid for this map.
+    /// Generations keep increasing across the restore, so handles given out by this map
+    /// that are not valid for `other` are never given out again.
+    pub fn restore_from(&mut self, other: &Self)
+    where
+        T: Clone,
+    {
+        self.link_map.restore_from(&other.link_map);
+        let id = self.link_map.id();
+        let back_link = other.back_link.iter().map(|handle| {
+            let (index, gen, _) = handle.into_raw_parts();
+            Handle::from_raw_parts(index, gen, id)
+        });
+        self.back_link = back_link.collect();
+        self.values.clone_from(&other.values);
+    }
+
+    /// Returns all the values in the map as a tightly packed slice.
+    ///
+    /// The order of the slice matches the order of [`handles`](Self::handles).
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns all the values in the map as a tightly packed mutable slice.
+    ///
+    /// The order of the slice matches the order of [`handles`](Self::handles).
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    /// Returns the handles of the map along with all the values as a mutable slice.
+    ///
+    /// The order of the handles matches the order of the slice.
+    #[inline]
+    pub fn split_mut(&mut self) -> (Handles<'_, T>, &mut [T]) {
+        let handles = Handles {
+            inner: self.back_link.iter(),
+        };
+        (handles, &mut self.values)
+    }
+
     /// Returns an iterator over the handles of the map.
     #[inline]
     pub fn handles(&self) -> Handles<'_, T> {
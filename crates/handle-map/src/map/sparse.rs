@@ -5,10 +5,14 @@ use std::{
 
 use crate::{map::HandleMapId, Handle};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct SparseEntry<T> {
     handle: Handle<T>,
     data: Option<T>,
+    /// The lowest generation this slot may give out after its current handle.
+    ///
+    /// This is only raised above the current generation by [`SparseHandleMap::restore_from`].
+    min_gen: u16,
 }
 
 impl<T> SparseEntry<T> {
@@ -17,6 +21,17 @@ impl<T> SparseEntry<T> {
         Self {
             handle,
             data: Some(data),
+            min_gen: 0,
+        }
+    }
+
+    /// Returns the first generation that has not been given out by this slot.
+    #[inline]
+    fn next_gen(&self) -> u16 {
+        let gen = self.handle.generation();
+        match self.data {
+            Some(_) => gen.wrapping_add(1).max(self.min_gen),
+            None => gen.max(self.min_gen),
         }
     }
 }
@@ -31,7 +46,7 @@ impl<T> SparseEntry<T> {
 /// the map will take up as much space as the max amount of items that used to be inside.
 ///
 /// Since the map uses a [`Handle`] for indexing, the max length of the map is limited to `u32::MAX`.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SparseHandleMap<T> {
     id: u16,
     values: Vec<SparseEntry<T>>,
@@ -89,6 +104,21 @@ impl<T> SparseHandleMap<T> {
         self.values.len() == self.open_slots.len()
     }
 
+    /// Returns the number of items the map can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.values.capacity()
+    }
+
+    /// Returns the number of bytes allocated by the map.
+    ///
+    /// This does not include any heap memory owned by the items themselves.
+    #[inline]
+    pub fn allocated_bytes(&self) -> usize {
+        self.values.capacity() * std::mem::size_of::<SparseEntry<T>>()
+            + self.open_slots.capacity() * std::mem::size_of::<usize>()
+    }
+
     /// Returns the handle that will be provided after `count` inserts.
     ///
     /// Is only true for chains of inserts.
@@ -112,7 +142,7 @@ impl<T> SparseHandleMap<T> {
             Some(index) => {
                 let entry = &mut self.values[index];
                 entry.data = Some(data);
-                entry.handle.clone()
+                entry.handle
             }
             None => {
                 let index = self.values.len();
@@ -124,7 +154,7 @@ impl<T> SparseHandleMap<T> {
                 }
 
                 let handle = Handle::from_raw_parts(index as u32, 0, self.id);
-                self.values.push(SparseEntry::new(handle.clone(), data));
+                self.values.push(SparseEntry::new(handle, data));
                 handle
             }
         }
@@ -161,6 +191,25 @@ impl<T> SparseHandleMap<T> {
         }
     }
 
+    /// Returns mutable references to the data associated with each handle in `handles`.
+    ///
+    /// Returns `None` if any handle is invalid, or if any two handles are the same.
+    #[inline]
+    pub fn get_disjoint_mut<const N: usize>(
+        &mut self,
+        handles: [Handle<T>; N],
+    ) -> Option<[&mut T; N]> {
+        if !handles.iter().all(|handle| self.contains(*handle)) {
+            return None;
+        }
+
+        let entries = self
+            .values
+            .get_disjoint_mut(handles.map(|handle| handle.uindex()))
+            .ok()?;
+        Some(entries.map(|entry| entry.data.as_mut().unwrap()))
+    }
+
     /// Removes and returns the data for `handle`.
     ///
     /// Returns `None` if `handle` is invalid for this map.
@@ -168,9 +217,9 @@ impl<T> SparseHandleMap<T> {
     pub fn remove(&mut self, handle: Handle<T>) -> Option<T> {
         match self.values.get_mut(handle.uindex()) {
             Some(entry) if entry.handle == handle => {
-                let (index, gen, meta) = entry.handle.clone().into_raw_parts();
-                entry.handle = Handle::from_raw_parts(index, gen.wrapping_add(1), meta);
-                let data = std::mem::replace(&mut entry.data, None);
+                let (index, _, meta) = entry.handle.into_raw_parts();
+                entry.handle = Handle::from_raw_parts(index, entry.next_gen(), meta);
+                let data = entry.data.take();
                 self.open_slots.push_back(handle.uindex());
                 data
             }
@@ -178,6 +227,48 @@ impl<T> SparseHandleMap<T> {
         }
     }
 
+    /// Replaces the contents of this map with a clone of the contents of `other`.
+    ///
+    /// Handles that are valid for `other` become valid for this map.
+    /// Generations keep increasing across the restore, so handles given out by this map
+    /// that are not valid for `other` are never given out again.
+    pub fn restore_from(&mut self, other: &Self)
+    where
+        T: Clone,
+    {
+        let len = self.values.len().max(other.values.len());
+        let mut values = Vec::with_capacity(len);
+        for index in 0..len {
+            let next_gen = self.values.get(index).map_or(0, |entry| entry.next_gen());
+            let entry = match other.values.get(index) {
+                Some(entry) if entry.data.is_some() => SparseEntry {
+                    handle: Handle::from_raw_parts(
+                        index as u32,
+                        entry.handle.generation(),
+                        self.id,
+                    ),
+                    data: entry.data.clone(),
+                    min_gen: next_gen.max(entry.min_gen),
+                },
+                entry => {
+                    let gen = next_gen.max(entry.map_or(0, |entry| entry.next_gen()));
+                    SparseEntry {
+                        handle: Handle::from_raw_parts(index as u32, gen, self.id),
+                        data: None,
+                        min_gen: gen,
+                    }
+                }
+            };
+            values.push(entry);
+        }
+
+        // slots that only exist in this map are open after the slots open in `other`
+        let mut open_slots = other.open_slots.clone();
+        open_slots.extend(other.values.len()..len);
+        self.values = values;
+        self.open_slots = open_slots;
+    }
+
     /// Returns an iterator over the map.
     #[inline]
     pub fn iter(&self) -> Iter<'_, T> {
@@ -310,4 +401,42 @@ mod tests {
         assert!(map.contains(future_handle2));
         assert!(map.get(future_handle2).unwrap() == &6789);
     }
+
+    #[test]
+    fn disjoint() {
+        let mut map = SparseHandleMap::<u32>::new();
+        let handle1 = map.insert(1);
+        let handle2 = map.insert(2);
+
+        let [v1, v2] = map.get_disjoint_mut([handle1, handle2]).unwrap();
+        std::mem::swap(v1, v2);
+        assert!(map[handle1] == 2);
+        assert!(map[handle2] == 1);
+        assert!(map.get_disjoint_mut([handle1, handle1]).is_none());
+
+        map.remove(handle2);
+        assert!(map.get_disjoint_mut([handle1, handle2]).is_none());
+    }
+
+    #[test]
+    fn restore() {
+        let mut map = SparseHandleMap::<u32>::new();
+        let handle1 = map.insert(1);
+        let saved = map.clone();
+
+        // handles given out after the clone are not given out again after restoring
+        map.remove(handle1);
+        let handle2 = map.insert(2);
+        let handle3 = map.insert(3);
+        map.restore_from(&saved);
+        assert!(map[handle1] == 1);
+        assert!(!map.contains(handle2) && !map.contains(handle3));
+
+        map.remove(handle1);
+        let handle4 = map.insert(4);
+        let handle5 = map.insert(5);
+        assert!(handle4 != handle3 && handle5 != handle2);
+        assert!(!map.contains(handle2) && !map.contains(handle3));
+        assert!(map.len() == 2);
+    }
 }
@@ -56,13 +56,17 @@ impl<'a> Drop for MilkTeaExecutor<'a> {
 
 impl<'a> MilkTeaExecutor<'a> {
     pub fn trigger<T: 'static>(&self, world: &mut World, event: T) -> T {
-        let mut milk_tea = MilkTea {
-            target_defer: Vec::new(),
-            delta_time: self.delta_time,
-            game_time: self.game_time,
-            exiting: self.target.exiting(),
-            event,
-        };
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "milk_tea",
+            event = std::any::type_name::<T>(),
+            game_time = self.game_time,
+            delta_time = self.delta_time,
+        )
+        .entered();
+
+        let exiting = self.target.exiting();
+        let mut milk_tea = MilkTea::new(event, self.delta_time, self.game_time, exiting);
 
         world.trigger(&mut milk_tea);
 
@@ -80,21 +84,24 @@ impl<'a> MilkTeaExecutor<'a> {
     }
 }
 
+/// An event loop request deferred by a listener until all listeners have run.
+pub type TargetDefer = Box<dyn FnOnce(&mut World, &EventLoopWindowTarget<()>)>;
+
 pub struct MilkTea<T> {
-    target_defer: Vec<Box<dyn FnOnce(&mut World, &EventLoopWindowTarget<()>)>>,
+    target_defer: Vec<TargetDefer>,
     delta_time: f32,
     game_time: f32,
     exiting: bool,
     event: T,
 }
 
-impl<'a, T> DerefMut for MilkTea<T> {
+impl<T> DerefMut for MilkTea<T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.event
     }
 }
 
-impl<'a, T> Deref for MilkTea<T> {
+impl<T> Deref for MilkTea<T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -103,6 +110,24 @@ impl<'a, T> Deref for MilkTea<T> {
 }
 
 impl<T> MilkTea<T> {
+    pub(crate) fn new(event: T, delta_time: f32, game_time: f32, exiting: bool) -> Self {
+        Self {
+            target_defer: Vec::new(),
+            delta_time,
+            game_time,
+            exiting,
+            event,
+        }
+    }
+
+    /// Removes and returns the event loop requests that were deferred by listeners.
+    ///
+    /// Deferred requests need an event loop target to run, so they are handed back when testing.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn take_deferred(&mut self) -> Vec<TargetDefer> {
+        std::mem::take(&mut self.target_defer)
+    }
+
     pub fn game_time(&self) -> f32 {
         self.game_time
     }
@@ -1,5 +1,7 @@
 pub mod events;
 pub mod pearls;
 pub mod run;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod testing;
 
 pub use run::run;
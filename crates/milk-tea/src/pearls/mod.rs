@@ -1,3 +1,5 @@
+pub mod task;
 pub mod window;
 
+pub use task::Task;
 pub use window::Window;
@@ -0,0 +1,335 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+use boba_core::{
+    pearl::{EventSource, Listener},
+    world::{Link, PearlView, WorldQueue},
+    Pearl,
+};
+use extension_trait::extension_trait;
+
+use crate::events::{app::Update, MilkTea};
+
+type WorldFn = Box<dyn FnOnce(&mut WorldQueue)>;
+type TaskFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+#[derive(Default)]
+struct TaskState {
+    tick: Cell<u64>,
+    game_time: Cell<f32>,
+    delta_time: Cell<f32>,
+    /// World accesses requested by the task since it was last polled, in request order.
+    access: RefCell<VecDeque<WorldFn>>,
+}
+
+/// A pearl that drives an async task once per [`Update`].
+///
+/// Tasks are attached to an owning pearl using [`SpawnTask::spawn_task`],
+/// and are cancelled on the next update after the owner is removed.
+pub struct Task {
+    owner: Link<()>,
+    alive: fn(&WorldQueue, Link<()>) -> bool,
+    state: Rc<TaskState>,
+    future: Option<TaskFuture>,
+}
+
+impl Pearl for Task {
+    fn register(source: &mut impl EventSource<Self>) {
+        source.listen::<MilkTea<Update>>();
+    }
+}
+
+impl Listener<MilkTea<Update>> for Task {
+    fn trigger(mut pearl: PearlView<Self>, event: &mut MilkTea<Update>) {
+        let state = pearl.state.clone();
+        state.tick.set(state.tick.get() + 1);
+        state.game_time.set(event.game_time());
+        state.delta_time.set(event.delta_time());
+
+        let mut context = Context::from_waker(Waker::noop());
+        loop {
+            // cancel the task if its owner was removed
+            if !(pearl.alive)(pearl.world(), pearl.owner) {
+                pearl.future = None;
+                pearl.destroy_self();
+                return;
+            }
+
+            let Some(future) = pearl.future.as_mut() else {
+                return;
+            };
+
+            if future.as_mut().poll(&mut context).is_ready() {
+                pearl.future = None;
+                pearl.destroy_self();
+                return;
+            }
+
+            // run every world access requested by the task, then poll it again
+            let pending = std::mem::take(&mut *state.access.borrow_mut());
+            if pending.is_empty() {
+                return;
+            }
+
+            for access in pending {
+                access(pearl.world_mut());
+            }
+        }
+    }
+}
+
+/// The context passed to a task, used to wait and access the world between awaits.
+pub struct TaskContext<P> {
+    owner: Link<P>,
+    state: Rc<TaskState>,
+}
+
+impl<P: Pearl> Clone for TaskContext<P> {
+    fn clone(&self) -> Self {
+        Self {
+            owner: self.owner,
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<P: Pearl> TaskContext<P> {
+    pub fn link(&self) -> Link<P> {
+        self.owner
+    }
+
+    pub fn game_time(&self) -> f32 {
+        self.state.game_time.get()
+    }
+
+    pub fn delta_time(&self) -> f32 {
+        self.state.delta_time.get()
+    }
+
+    /// Returns a future that resolves on the next update.
+    pub fn next_update(&self) -> NextUpdate {
+        NextUpdate {
+            state: self.state.clone(),
+            tick: None,
+        }
+    }
+
+    /// Returns a future that resolves after `secs` seconds of game time.
+    pub fn wait_secs(&self, secs: f32) -> WaitSecs {
+        WaitSecs {
+            state: self.state.clone(),
+            secs,
+            deadline: None,
+        }
+    }
+
+    /// Returns a future that resolves to the result of running `f` on the world.
+    pub fn world<R: 'static>(
+        &self,
+        f: impl FnOnce(&mut WorldQueue) -> R + 'static,
+    ) -> WorldAccess<R> {
+        let result = Rc::new(Cell::new(None));
+        let result_inner = result.clone();
+        WorldAccess {
+            state: self.state.clone(),
+            access: Some(Box::new(move |world| result_inner.set(Some(f(world))))),
+            result,
+        }
+    }
+
+    /// Returns a future that resolves to the result of running `f` on the owning pearl.
+    pub fn pearl<R: 'static>(&self, f: impl FnOnce(PearlView<P>) -> R + 'static) -> WorldAccess<R> {
+        let owner = self.owner;
+        self.world(move |world| f(PearlView::new_unchecked(owner, world)))
+    }
+}
+
+pub struct NextUpdate {
+    state: Rc<TaskState>,
+    tick: Option<u64>,
+}
+
+impl Future for NextUpdate {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Self::Output> {
+        let current = self.state.tick.get();
+        match *self.tick.get_or_insert(current) < current {
+            true => Poll::Ready(()),
+            false => Poll::Pending,
+        }
+    }
+}
+
+pub struct WaitSecs {
+    state: Rc<TaskState>,
+    secs: f32,
+    deadline: Option<f32>,
+}
+
+impl Future for WaitSecs {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Self::Output> {
+        let now = self.state.game_time.get();
+        let secs = self.secs;
+        match now >= *self.deadline.get_or_insert(now + secs) {
+            true => Poll::Ready(()),
+            false => Poll::Pending,
+        }
+    }
+}
+
+pub struct WorldAccess<R> {
+    state: Rc<TaskState>,
+    access: Option<WorldFn>,
+    result: Rc<Cell<Option<R>>>,
+}
+
+impl<R> Future for WorldAccess<R> {
+    type Output = R;
+
+    fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(access) = self.access.take() {
+            self.state.access.borrow_mut().push_back(access);
+            return Poll::Pending;
+        }
+
+        match self.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => Poll::Pending,
+        }
+    }
+}
+
+#[extension_trait]
+pub impl<P: Pearl> SpawnTask<P> for PearlView<'_, '_, P> {
+    /// Spawns a task owned by this pearl, and returns a link to its [`Task`].
+    ///
+    /// The task is first polled on the next update.
+    fn spawn_task<F: Future<Output = ()> + 'static>(
+        &mut self,
+        f: impl FnOnce(TaskContext<P>) -> F,
+    ) -> Link<Task> {
+        let link = self.link();
+        let state = Rc::new(TaskState::default());
+        let future = f(TaskContext {
+            owner: link,
+            state: state.clone(),
+        });
+
+        self.world_mut().insert(Task {
+            owner: link.into_type(),
+            alive: |world, owner| world.contains(owner.into_type::<P>()),
+            state,
+            future: Some(Box::pin(future)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::poll_fn;
+
+    use crate::testing::{TestApp, WorldAssert};
+
+    use super::*;
+
+    struct Owner(Vec<&'static str>);
+
+    impl Pearl for Owner {}
+
+    fn spawn<F: Future<Output = ()> + 'static>(
+        app: &mut TestApp,
+        f: impl FnOnce(TaskContext<Owner>) -> F,
+    ) -> (Link<Owner>, Link<Task>) {
+        let owner = app.insert(Owner(Vec::new()));
+        let mut world = WorldQueue::new(app);
+        let task = PearlView::new_unchecked(owner, &mut world).spawn_task(f);
+        drop(world);
+        (owner, task)
+    }
+
+    fn log(cx: &TaskContext<Owner>, entry: &'static str) -> WorldAccess<()> {
+        cx.pearl(move |mut owner| owner.0.push(entry))
+    }
+
+    #[test]
+    fn spawn_and_wait() {
+        let mut app = TestApp::default();
+        app.set_delta_time(0.25);
+        let (owner, task) = spawn(&mut app, |cx| async move {
+            log(&cx, "start").await;
+            cx.next_update().await;
+            log(&cx, "next").await;
+            cx.wait_secs(0.5).await;
+            log(&cx, "done").await;
+        });
+
+        // the task is first polled on the next update
+        app.assert_contains(task);
+        assert!(app.get(owner).unwrap().0.is_empty());
+        app.update();
+        assert!(app.get(owner).unwrap().0 == ["start"]);
+        app.update();
+        assert!(app.get(owner).unwrap().0 == ["start", "next"]);
+        app.update();
+        assert!(app.get(owner).unwrap().0 == ["start", "next"]);
+        app.update();
+        assert!(app.get(owner).unwrap().0 == ["start", "next", "done"]);
+        app.assert_missing(task);
+        app.assert_contains(owner);
+    }
+
+    #[test]
+    fn cancel_with_owner() {
+        let mut app = TestApp::default();
+        let (owner, task) = spawn(&mut app, |cx| async move {
+            loop {
+                cx.next_update().await;
+            }
+        });
+
+        app.run_updates(2);
+        app.assert_contains(task);
+        app.remove(owner);
+        app.update();
+        app.assert_missing(task);
+    }
+
+    #[test]
+    fn concurrent_world_access() {
+        let mut app = TestApp::default();
+        let (owner, task) = spawn(&mut app, |cx| async move {
+            let mut first = std::pin::pin!(cx.world(|_| 1));
+            let mut second = std::pin::pin!(cx.world(|_| 2));
+            let (mut a, mut b) = (None, None);
+            let (a, b) = poll_fn(|context| {
+                if let Poll::Ready(value) = first.as_mut().poll(context) {
+                    a = Some(value);
+                }
+                if let Poll::Ready(value) = second.as_mut().poll(context) {
+                    b = Some(value);
+                }
+                match (a, b) {
+                    (Some(a), Some(b)) => Poll::Ready((a, b)),
+                    _ => Poll::Pending,
+                }
+            })
+            .await;
+            assert!((a, b) == (1, 2));
+            log(&cx, "joined").await;
+        });
+
+        // both accesses are run during the same update
+        app.update();
+        assert!(app.get(owner).unwrap().0 == ["joined"]);
+        app.assert_missing(task);
+    }
+}
@@ -33,8 +33,18 @@ pub fn run_with_flow(world: &mut World, poll: bool) {
     // create a timer to create update events every loop
     let mut timer = EventTimer::new();
 
+    // the span around the current loop iteration, from new events until the update
+    #[cfg(feature = "tracing")]
+    let mut frame_span: Option<tracing::span::EnteredSpan> = None;
+
     // run the event loop
     if let Err(error) = event_loop.run(move |event, target| {
+        #[cfg(feature = "tracing")]
+        if let Event::NewEvents(_) = event {
+            frame_span.take();
+            frame_span = Some(tracing::info_span!(boba_core::trace::FRAME_SPAN).entered());
+        }
+
         let executor = timer.next(target);
         match event {
             Event::NewEvents(StartCause::Init) => {
@@ -63,6 +73,9 @@ pub fn run_with_flow(world: &mut World, poll: bool) {
             },
             Event::AboutToWait => {
                 executor.trigger(world, Update::new());
+
+                #[cfg(feature = "tracing")]
+                frame_span.take();
             }
             _ => (),
         }
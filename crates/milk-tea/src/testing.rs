@@ -0,0 +1,278 @@
+use std::ops::{Deref, DerefMut};
+
+use boba_core::{world::Link, Pearl, World};
+use extension_trait::extension_trait;
+use winit::{dpi::PhysicalSize, event_loop::EventLoopWindowTarget, window::WindowId};
+
+use crate::events::{
+    app::{Init, Resume, Suspend, Update},
+    milktea::TargetDefer,
+    window::{Close, Focus, Redraw, Resize},
+    MilkTea,
+};
+
+/// A fake app that triggers [`MilkTea`] events on a world with simulated time,
+/// used to test pearls and listeners without a window or event loop.
+///
+/// Exit requests are recorded instead of exiting,
+/// and event loop requests made using `target_defer` are kept instead of run.
+/// They can be inspected using [`take_deferred`](Self::take_deferred),
+/// or run against a real event loop target using [`run_deferred`](Self::run_deferred).
+pub struct TestApp {
+    world: World,
+    delta_time: f32,
+    game_time: f32,
+    exiting: bool,
+    deferred: Vec<TargetDefer>,
+}
+
+impl Default for TestApp {
+    fn default() -> Self {
+        Self::new(World::new())
+    }
+}
+
+impl Deref for TestApp {
+    type Target = World;
+
+    fn deref(&self) -> &Self::Target {
+        &self.world
+    }
+}
+
+impl DerefMut for TestApp {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.world
+    }
+}
+
+impl TestApp {
+    /// Creates an app for `world` that advances 1/60th of a second every update.
+    pub fn new(world: World) -> Self {
+        Self {
+            world,
+            delta_time: 1. / 60.,
+            game_time: 0.,
+            exiting: false,
+            deferred: Vec::new(),
+        }
+    }
+
+    pub fn delta_time(&self) -> f32 {
+        self.delta_time
+    }
+
+    /// Sets the time that each update advances the game time by.
+    ///
+    /// The delta time must be positive, otherwise [`run_secs`](Self::run_secs) would never finish.
+    pub fn set_delta_time(&mut self, delta_time: f32) {
+        assert!(delta_time > 0., "delta time must be positive, got {delta_time}");
+        self.delta_time = delta_time;
+    }
+
+    pub fn game_time(&self) -> f32 {
+        self.game_time
+    }
+
+    pub fn set_game_time(&mut self, game_time: f32) {
+        self.game_time = game_time;
+    }
+
+    /// Returns `true` if any listener has called [`MilkTea::exit_app`].
+    pub fn exit_requested(&self) -> bool {
+        self.exiting
+    }
+
+    /// Returns the number of event loop requests deferred by listeners that have not been taken or run.
+    pub fn deferred_requests(&self) -> usize {
+        self.deferred.len()
+    }
+
+    /// Removes and returns the event loop requests deferred by listeners, in the order they were made.
+    pub fn take_deferred(&mut self) -> Vec<TargetDefer> {
+        std::mem::take(&mut self.deferred)
+    }
+
+    /// Runs every event loop request deferred by listeners using `target`.
+    pub fn run_deferred(&mut self, target: &EventLoopWindowTarget<()>) {
+        for deferred in self.take_deferred() {
+            deferred(&mut self.world, target);
+        }
+    }
+
+    /// Triggers `event` wrapped in a [`MilkTea`] using the current times,
+    /// and returns it after every listener has run.
+    pub fn trigger<T: 'static>(&mut self, event: T) -> MilkTea<T> {
+        let mut milk_tea = MilkTea::new(event, self.delta_time, self.game_time, self.exiting);
+        self.world.trigger(&mut milk_tea);
+        self.exiting = milk_tea.exiting();
+        self.deferred.extend(milk_tea.take_deferred());
+        milk_tea
+    }
+
+    pub fn init(&mut self) -> MilkTea<Init> {
+        self.trigger(Init::new())
+    }
+
+    pub fn resume(&mut self) -> MilkTea<Resume> {
+        self.trigger(Resume::new())
+    }
+
+    pub fn suspend(&mut self) -> MilkTea<Suspend> {
+        self.trigger(Suspend::new())
+    }
+
+    /// Advances the game time by the delta time, then triggers an [`Update`].
+    pub fn update(&mut self) -> MilkTea<Update> {
+        self.game_time += self.delta_time;
+        self.trigger(Update::new())
+    }
+
+    /// Runs `count` updates.
+    pub fn run_updates(&mut self, count: usize) {
+        for _ in 0..count {
+            self.update();
+        }
+    }
+
+    /// Runs updates until the game time has advanced by at least `secs`.
+    pub fn run_secs(&mut self, secs: f32) {
+        let end = self.game_time + secs;
+        while self.game_time < end {
+            self.update();
+        }
+    }
+
+    pub fn close(&mut self, id: WindowId) -> MilkTea<Close> {
+        self.trigger(Close::new(id))
+    }
+
+    pub fn redraw(&mut self, id: WindowId) -> MilkTea<Redraw> {
+        self.trigger(Redraw::new(id))
+    }
+
+    pub fn resize(&mut self, id: WindowId, size: PhysicalSize<u32>) -> MilkTea<Resize> {
+        self.trigger(Resize::new(id, size))
+    }
+
+    pub fn focus(&mut self, id: WindowId, focused: bool) -> MilkTea<Focus> {
+        self.trigger(Focus::new(id, focused))
+    }
+
+    pub fn into_world(self) -> World {
+        self.world
+    }
+}
+
+#[extension_trait]
+pub impl WorldAssert for World {
+    /// Asserts that the world contains exactly `count` pearls of type `P`.
+    #[track_caller]
+    fn assert_count<P: Pearl>(&self, count: usize) {
+        let len = self.len::<P>();
+        let name = std::any::type_name::<P>();
+        assert!(len == count, "expected {count} of {name}, found {len}");
+    }
+
+    /// Asserts that the pearl at `link` exists.
+    #[track_caller]
+    fn assert_contains<P: Pearl>(&self, link: Link<P>) {
+        assert!(self.contains(link), "expected {link} to exist");
+    }
+
+    /// Asserts that the pearl at `link` does not exist.
+    #[track_caller]
+    fn assert_missing<P: Pearl>(&self, link: Link<P>) {
+        assert!(!self.contains(link), "expected {link} to be removed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use boba_core::{
+        pearl::{EventSource, Listener},
+        world::{Inserted, PearlView},
+    };
+
+    use crate::pearls::{task::SpawnTask, Task};
+
+    use super::*;
+
+    struct Fuse(u32);
+
+    impl Pearl for Fuse {
+        fn register(source: &mut impl EventSource<Self>) {
+            source.listen::<MilkTea<Update>>();
+        }
+    }
+
+    impl Listener<MilkTea<Update>> for Fuse {
+        fn trigger(mut pearl: PearlView<Self>, event: &mut MilkTea<Update>) {
+            pearl.0 -= 1;
+            if pearl.0 == 0 {
+                event.exit_app();
+                pearl.destroy_self();
+            }
+        }
+    }
+
+    struct Spawner;
+
+    impl Pearl for Spawner {
+        fn register(source: &mut impl EventSource<Self>) {
+            source.listen::<MilkTea<Update>>();
+        }
+    }
+
+    impl Listener<MilkTea<Update>> for Spawner {
+        fn trigger(_: PearlView<Self>, event: &mut MilkTea<Update>) {
+            event.target_defer(|world, _| {
+                world.insert(Timer);
+            });
+        }
+    }
+
+    struct Timer;
+
+    impl Pearl for Timer {
+        fn on_insert(mut pearl: Inserted<Self>) {
+            pearl.spawn_task(|cx| async move {
+                cx.wait_secs(0.5).await;
+                cx.pearl(|mut pearl| pearl.destroy_self()).await;
+            });
+        }
+    }
+
+    #[test]
+    fn simulate_updates() {
+        let mut app = TestApp::default();
+        let fuse = app.insert(Fuse(3));
+        app.insert(Timer);
+
+        app.run_updates(2);
+        app.assert_contains(fuse);
+        assert!(!app.exit_requested());
+
+        app.update();
+        app.assert_missing(fuse);
+        assert!(app.exit_requested());
+
+        app.assert_count::<Timer>(1);
+        app.run_secs(0.5);
+        app.assert_count::<Timer>(0);
+        app.assert_count::<Task>(0);
+    }
+
+    #[test]
+    fn keep_deferred() {
+        let mut app = TestApp::default();
+        app.insert(Spawner);
+
+        app.run_updates(2);
+        assert!(app.deferred_requests() == 2);
+        let deferred = app.take_deferred();
+        assert!(deferred.len() == 2);
+        assert!(app.deferred_requests() == 0);
+        app.assert_count::<Timer>(0);
+    }
+}
@@ -1,6 +1,6 @@
 use boba_3d::{glam::Mat4, Transform};
 use boba_core::{
-    world::{Link, PearlView},
+    world::{Link, PearlView, Transferred},
     Pearl,
 };
 use extension_trait::extension_trait;
@@ -9,7 +9,10 @@ use wgpu::Texture;
 use crate::{events::TaroRender, renderer::Hardware};
 
 pub struct TaroCamera {
-    pub transform: Link<Transform>,
+    /// The transform the camera renders from.
+    ///
+    /// This is cleared if the camera is transferred to another world without its transform.
+    pub transform: Option<Link<Transform>>,
     pub fovy: f32,
     pub znear: f32,
     pub zfar: f32,
@@ -17,12 +20,18 @@ pub struct TaroCamera {
     view_matrix: Mat4,
 }
 
-impl Pearl for TaroCamera {}
+impl Pearl for TaroCamera {
+    fn on_transfer(mut pearl: Transferred<Self>) {
+        // keep the transform only if it moved along with the camera
+        let remap = pearl.remap();
+        pearl.transform = pearl.transform.and_then(|transform| remap.get(transform));
+    }
+}
 
 impl TaroCamera {
     pub fn new(transform: Link<Transform>) -> Self {
         Self {
-            transform,
+            transform: Some(transform),
             fovy: 45.,
             znear: 0.1,
             zfar: 1000.,
@@ -35,7 +44,8 @@ impl TaroCamera {
 pub impl TaroCameraView for PearlView<'_, '_, TaroCamera> {
     fn render(&mut self, texture: &Texture, hardware: &Hardware) {
         // update view matrix
-        if let Some(transform) = self.world().get(self.transform) {
+        let transform = self.transform.and_then(|link| self.world().get(link));
+        if let Some(transform) = transform {
             self.view_matrix = transform.world_matrix();
         }
 
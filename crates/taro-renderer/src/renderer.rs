@@ -38,7 +38,7 @@ impl Renderer for TaroRenderer {
             }
         };
 
-        return true;
+        true
     }
 
     fn render(pearl: &mut PearlView<Window<Self>>) {
@@ -120,7 +120,7 @@ impl Hardware {
 
     fn instance() -> &'static Instance {
         static INSTANCE: OnceCell<Instance> = OnceCell::new();
-        INSTANCE.get_or_init(|| Instance::default())
+        INSTANCE.get_or_init(Instance::default)
     }
 
     async fn build_hardware(surface: &Surface<'static>) -> Self {
@@ -27,7 +27,7 @@ fn main() {
     world.insert(TaroSentinel);
 
     // add the custom FPS printer pearl
-    world.insert(FpsPrinter::default());
+    world.insert(FpsPrinter);
 
     // run the world using milk tea
     milk_tea::run(&mut world);
@@ -13,7 +13,7 @@ impl Pearl for SignalEmitter {
 
 impl Listener<MilkTea<Update>> for SignalEmitter {
     fn trigger(mut pearl: PearlView<Self>, event: &mut MilkTea<Update>) {
-        let signal = pearl.signal.build(format!("Hello, World!"));
+        let signal = pearl.signal.build("Hello, World!".to_string());
         pearl.world_mut().send_signal(signal);
         event.exit_app();
     }
@@ -0,0 +1,52 @@
+use boba_engine::prelude::*;
+
+pub struct Countdown {
+    remaining: u32,
+    done: bool,
+}
+
+impl Pearl for Countdown {
+    fn register(source: &mut impl EventSource<Self>) {
+        source.listen::<MilkTea<Update>>();
+    }
+
+    fn on_insert(mut pearl: Inserted<Self>) {
+        pearl.spawn_task(|ctx| async move {
+            loop {
+                let remaining = ctx.pearl(|view| view.remaining).await;
+                if remaining == 0 {
+                    break;
+                }
+
+                println!("Countdown: {remaining}");
+                ctx.wait_secs(1.).await;
+                ctx.pearl(|mut view| view.remaining -= 1).await;
+            }
+
+            println!("Liftoff!");
+            ctx.pearl(|mut view| view.done = true).await;
+        });
+    }
+}
+
+impl Listener<MilkTea<Update>> for Countdown {
+    fn trigger(pearl: PearlView<Self>, event: &mut MilkTea<Update>) {
+        if pearl.done {
+            event.exit_app();
+        }
+    }
+}
+
+fn main() {
+    env_logger::init();
+    let mut world = World::new();
+
+    // create a countdown that runs as an async task
+    world.insert(Countdown {
+        remaining: 3,
+        done: false,
+    });
+
+    // run the world using milk tea
+    milk_tea::run(&mut world);
+}
@@ -2,17 +2,18 @@ pub use boba_core as core;
 
 pub mod prelude {
     pub use boba_core::{
-        pearl::{Event, EventSource, Listener},
-        world::{Inserted, Link, PearlView, Removed},
+        pearl::{BatchListener, Event, EventFamily, EventSource, FamilyListener, Listener},
+        query::{Query, Responder},
+        world::{BatchView, Inserted, Link, PearlView, Removed},
         Pearl, World,
     };
 
-    pub use boba_signal::{Signal, SignalBuilder, SignalRegister, WorldSignalExt};
+    pub use boba_signal::{Connection, Signal, SignalBuilder, SignalRegister, WorldSignalExt};
 
     pub use boba_3d::{glam::*, transform::TransformView, Transform};
     pub use milk_tea::{
         events::{app::Update, MilkTea},
-        pearls::Window,
+        pearls::{task::SpawnTask, Window},
     };
     pub use taro_renderer::{
         pearls::{TaroCamera, TaroSentinel},